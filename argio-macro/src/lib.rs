@@ -1,198 +1,4456 @@
+mod runtime;
+
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, visit_mut::VisitMut, Token};
+use syn::{parse_macro_input, parse_quote, spanned::Spanned, visit_mut::VisitMut, Token};
 
 /// A macro to convert function input and output to stdio
 #[proc_macro_attribute]
 pub fn argio(attr: TokenStream, item: TokenStream) -> TokenStream {
     let attr = parse_macro_input!(attr as ArgioAttr);
     let item = parse_macro_input!(item as syn::ItemFn);
+    match expand_argio(attr, item) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
 
-    let vis = item.vis;
-    let name = &item.sig.ident;
-    let ret_type = item.sig.output;
-    let args = &item.sig.inputs;
-    let body = item.block.as_ref();
+fn expand_argio(
+    mut attr: ArgioAttr,
+    mut item: syn::ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    // `#[proconio::fastout]` would write the output bypassing the generated code, so it is
+    // replaced by buffering the output as `fastout` does. It is removed below if it is put below
+    // `#[argio]`, and its expansion is unwrapped if it is put above.
+    let fastout_below = item.attrs.iter().any(is_fastout);
+    let fastout_above = unwrap_fastout(&mut item.block);
+    if (fastout_below || fastout_above) && !attr.flush_given {
+        attr.flush = Flush::AtEnd;
+    }
+
+    if attr.try_body {
+        if item.sig.asyncness.is_some() {
+            return Err(syn::Error::new_spanned(
+                item.sig.asyncness,
+                "argio: try cannot be used on async fn",
+            ));
+        }
+        *item.block = try_block(&item.block, &item.sig.output);
+    }
+
+    let (mut tests, stress, property) = companions(&item.attrs)?;
+    tests.extend(doc_samples(&item.attrs, &item.sig.ident)?);
+
+    let fn_attrs = item
+        .attrs
+        .iter()
+        .filter(|attr| {
+            !["test", "stress", "property"]
+                .iter()
+                .any(|name| is_companion_attr(attr, name))
+        })
+        .filter(|attr| !is_fastout(attr))
+        .map(|attr| syn::Attribute {
+            style: syn::AttrStyle::Outer,
+            ..attr.clone()
+        })
+        .collect::<Vec<_>>();
+    let ret_type = item.sig.output.clone();
+    check_sig(&item.sig)?;
+    let mut args = Param::parse_all(&item.sig.inputs)?;
+
+    if attr.json {
+        if let Some(arg) = args.iter().find(|arg| arg.attr.until_blank || arg.lines) {
+            return Err(syn::Error::new_spanned(
+                &arg.arg,
+                "argio: until_blank and Line cannot be used with format = json",
+            ));
+        }
+    }
+
+    let property = match property {
+        Some(_) if attr.multicase.is_some() => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "argio: argio::property cannot be used with multicase",
+            ))
+        }
+        Some(_) if attr.json => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "argio: argio::property cannot be used with format = json",
+            ))
+        }
+        Some(property) => Some((property, input_generator(&args)?)),
+        None => None,
+    };
+
+    if item.sig.asyncness.is_some() && attr.executor.is_none() {
+        return Err(syn::Error::new_spanned(
+            item.sig.asyncness,
+            "argio: async fn requires executor = pollster or futures, or runtime = tokio",
+        ));
+    }
+    if item.sig.asyncness.is_none() {
+        if let Some((_, span)) = &attr.executor {
+            return Err(syn::Error::new(
+                *span,
+                "argio: executor and runtime can only be used on async fn",
+            ));
+        }
+    }
+
+    if attr.entry && item.sig.ident == "main" {
+        return Err(syn::Error::new_spanned(
+            &item.sig.ident,
+            "argio: entry cannot be used on main",
+        ));
+    }
 
-    let ret_var: syn::Ident = parse_quote! { ret };
-    let wrapped: syn::Expr = if let Some(wrapper) = &attr.output {
-        parse_quote! { #wrapper(#ret_var) }
+    let (entry_fn, vis, name, body, fn_attrs) = if attr.entry {
+        let entry_fn = entry_fn(&item, &args, &fn_attrs)?;
+        let name = &item.sig.ident;
+        let call_args = args
+            .iter()
+            .map(|arg| pat_expr(&arg.arg.pat))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let call: syn::Expr = if item.sig.asyncness.is_some() {
+            parse_quote! { #name(#(#call_args),*).await }
+        } else {
+            parse_quote! { #name(#(#call_args),*) }
+        };
+        let body: syn::Block = parse_quote! {{
+            #call
+        }};
+        // The values read in main are just passed to the entry function
+        for arg in &mut args {
+            ImmutableRewriter.visit_pat_mut(&mut arg.arg.pat);
+        }
+        (
+            entry_fn,
+            syn::Visibility::Inherited,
+            parse_quote! { main },
+            body,
+            vec![],
+        )
     } else {
-        parse_quote! { #ret_var }
+        (
+            quote! {},
+            item.vis.clone(),
+            item.sig.ident.clone(),
+            item.block.as_ref().clone(),
+            fn_attrs,
+        )
     };
 
-    let unit_type: syn::Type = parse_quote! {()};
+    let body: syn::Block = match &attr.executor {
+        Some((executor, _)) => {
+            let block_on = executor.block_on();
+            parse_quote! {{
+                #block_on(async move #body)
+            }}
+        }
+        None => body,
+    };
+    // The statements of the body are spliced into the generated closures, so that a body of a
+    // single expression is not left in redundant braces in the file written by `bundle`. A body
+    // with items keeps its block, so that the items do not clash with the parameters.
+    let body = if body
+        .stmts
+        .iter()
+        .any(|stmt| matches!(stmt, syn::Stmt::Item(_)))
+    {
+        quote! { #body }
+    } else {
+        let stmts = &body.stmts;
+        quote! { #(#stmts)* }
+    };
+
+    let ret_var: syn::Ident = parse_quote! { __argio_ret };
+    let wrapped: syn::Expr = if let Some((wrapper, args)) = &attr.output {
+        parse_quote! { #wrapper(#ret_var #(, #args)*) }
+    } else {
+        parse_quote! { #ret_var }
+    };
 
     let ret_type: syn::Type = match ret_type {
-        syn::ReturnType::Default => unit_type.clone(),
+        syn::ReturnType::Default => parse_quote! {()},
         syn::ReturnType::Type(_, ty) => parse_quote! { #ty },
     };
 
-    let print_code = if ret_type == unit_type {
+    // `Result<(), E>` is not printed, and an error is returned from the generated function.
+    let fallible = is_unit_result(&ret_type);
+    if fallible && attr.multicase.as_ref().is_some_and(|m| m.parallel) {
+        return Err(syn::Error::new_spanned(
+            &ret_type,
+            "argio: parallel cannot be used with a function returning Result<(), E>",
+        ));
+    }
+    let print_code = print_code(&attr, &ret_type, &ret_var, &wrapped);
+
+    let input = Input::new(&attr, &args);
+    let setup = Setup::new(&attr, &input, &args);
+
+    let (option_args, input_args): (Vec<_>, Vec<_>) =
+        args.iter().partition(|arg| arg.is_external());
+    let (global_args, case_args): (Vec<_>, Vec<_>) = if attr.multicase.is_some() {
+        input_args.into_iter().partition(|arg| arg.attr.global)
+    } else {
+        (vec![], input_args)
+    };
+    let case_id: syn::Ident = parse_quote! { __argio_case_id };
+    let case = attr.multicase.as_ref().map(|_| &case_id);
+    let read_args = case_args_code(&attr, &input, case, &case_args)?;
+    let gen = Codegen {
+        attr: &attr,
+        read_args,
+        input,
+        args: &args,
+        global_args,
+        case_args,
+        body,
+        ret_type,
+        ret_var,
+        wrapped,
+        print_code,
+        case_id,
+        cases: parse_quote! { __argio_cases },
+        set_id: parse_quote! { __argio_set_id },
+        sets: parse_quote! { __argio_sets },
+    };
+    let read_options = option_args_code(&option_args);
+    let ret = match &attr.multicase {
+        // The options are read once before the other parameters.
+        Some(multicase) => gen.multicase(multicase, &setup, read_options)?,
+        None => gen.single(&setup, read_options),
+    };
+
+    let (main_ret, ret) = if fallible {
+        let ret_type = &gen.ret_type;
+        (
+            quote! { -> #ret_type },
+            quote! {
+                #ret
+                Ok(())
+            },
+        )
+    } else {
+        (quote! {}, ret)
+    };
+    let ret = match attr.stack_size {
+        Some(size) => quote! {
+            __argio::io::spawn_with_stack(#size, move || #main_ret {
+                #ret
+            })
+        },
+        None => ret,
+    };
+    let run = if fallible {
+        quote! { || #name().unwrap() }
+    } else {
+        quote! { #name }
+    };
+    let main_fn = MainFn {
+        entry_fn,
+        fn_attrs,
+        vis,
+        name,
+        main_ret,
+        body: ret,
+        run,
+    };
+
+    let test_fns = test_fns(
+        &attr,
+        &item.sig.ident,
+        tests,
+        stress,
+        property,
+        &main_fn.run,
+    )?;
+
+    let expanded = main_fn.expand(&attr, &attr.krate);
+    if let Some(path) = &attr.dump {
+        write_file(path, &pretty(&expanded))?;
+    }
+    if let Some(path) = &attr.bundle {
+        let bundled = main_fn.expand(&attr, &parse_quote! { crate::argio });
+        bundle(path, &item, &bundled)?;
+    }
+    Ok(quote! {
+        #expanded
+        #test_fns
+    })
+}
+
+// Parses the attrs of the companions `argio::test`, `argio::stress` and `argio::property`.
+fn companions(
+    attrs: &[syn::Attribute],
+) -> syn::Result<(Vec<SampleTest>, Option<Stress>, Option<Property>)> {
+    let mut tests = vec![];
+    let mut stress = None;
+    let mut property = None;
+    for attr in attrs {
+        if is_companion_attr(attr, "test") {
+            tests.push(attr.parse_args::<SampleTest>()?);
+        } else if is_companion_attr(attr, "property") {
+            if property.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "argio: argio::property is given twice",
+                ));
+            }
+            property = Some(attr.parse_args::<Property>()?);
+        } else if is_companion_attr(attr, "stress") {
+            if stress.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "argio: argio::stress is given twice",
+                ));
+            }
+            stress = Some(attr.parse_args::<Stress>()?);
+        }
+    }
+    Ok((tests, stress, property))
+}
+
+// Prints the return value of the function, or returns the error of `Result<(), E>`.
+fn print_code(
+    attr: &ArgioAttr,
+    ret_type: &syn::Type,
+    ret_var: &syn::Ident,
+    wrapped: &syn::Expr,
+) -> proc_macro2::TokenStream {
+    let unit_type: syn::Type = parse_quote! {()};
+    if *ret_type == unit_type {
         quote! {}
+    } else if is_unit_result(ret_type) {
+        quote! {
+            if let Err(err) = #ret_var {
+                __argio::io::flush();
+                return Err(err);
+            }
+        }
+    } else if attr.json {
+        quote! {
+            println!("{}", __argio::json::to_string(&#ret_var));
+        }
+    } else if attr.output.is_none()
+        && !attr.interactive
+        && !attr.multicase.as_ref().is_some_and(|m| m.parallel)
+        && is_number(ret_type)
+    {
+        // Primitive numbers are printed without `Display` with the feature `fast-fmt`. The
+        // parallel cases are printed with `println!` into the buffers of the cases instead.
+        quote! {
+            __argio::io::println_number(#ret_var);
+        }
     } else {
         quote! {
             println!("{}", #wrapped);
         }
-    };
+    }
+}
 
-    let input_macro: syn::Path = if let Some(path) = &attr.input {
-        path.clone()
+// Reads the parameters of a case, from a JSON object with `format = json`.
+fn case_args_code(
+    attr: &ArgioAttr,
+    input: &Input,
+    case: Option<&syn::Ident>,
+    case_args: &[&Param],
+) -> syn::Result<proc_macro2::TokenStream> {
+    if attr.json {
+        let reader = match &attr.input_str {
+            Some(input_str) => quote! { __argio::io::str_reader(#input_str) },
+            None if attr.input_file_arg => quote! { __argio::io::arg_reader() },
+            None => quote! { __argio::io::reader() },
+        };
+        json_args(case_args, reader)
     } else {
-        parse_quote! { argio::proconio::input }
-    };
+        Ok(read_args(input, case, case_args))
+    }
+}
+
+// The code run before and after solving the cases, which sets up and finishes the input, the
+// output and the reports.
+struct Setup {
+    init: proc_macro2::TokenStream,
+    flush_end: proc_macro2::TokenStream,
+    finish_source: proc_macro2::TokenStream,
+}
 
-    let ret = if let Some((fmt_str, fmt_span)) = &attr.multicase {
-        let (case_id, print_header) = if !fmt_str.contains('{') {
+impl Setup {
+    fn new(attr: &ArgioAttr, input: &Input, args: &[Param]) -> Self {
+        let capacity = match &attr.output_capacity {
+            Some(capacity) => quote! { ::core::option::Option::Some(#capacity) },
+            None => quote! { ::core::option::Option::None },
+        };
+        let init_source = source_code(attr, input, &capacity);
+        let buffer_output = output_code(attr, &capacity);
+        let (flush_print, interactive_input) = interactive_code(attr, input);
+        let body_input = body_input_code(input);
+        // The deadline starts before the input is read, and the body can ask the time left with
+        // `remaining!()`.
+        let (start_deadline, watch_deadline) = match &attr.deadline {
+            Some((_, millis)) => {
+                let watch = match &attr.on_deadline {
+                    Some(hook) => quote! {
+                        __argio_deadline.watch(#hook);
+                    },
+                    None => quote! {},
+                };
+                (
+                    quote! {
+                        let __argio_deadline = __argio::timing::Deadline::new(#millis);
+                        #[allow(unused_macros)]
+                        macro_rules! remaining {
+                            () => {
+                                __argio_deadline.remaining()
+                            };
+                        }
+                    },
+                    watch,
+                )
+            }
+            None => (quote! {}, quote! {}),
+        };
+        let mirror = match &attr.tee {
+            Some(prefix) => quote! {
+                __argio::io::mirror(#prefix);
+            },
+            None => quote! {},
+        };
+        let (start_stats, report_stats) = if attr.io_stats {
             (
-                parse_quote! { case_id },
                 quote! {
-                    print!(#fmt_str);
+                    __argio::io::start_stats();
+                },
+                quote! {
+                    __argio::io::report_stats();
                 },
             )
         } else {
-            let re = regex::Regex::new(r"^([^{]*)\{([^:}]+)(:[^}]+)?\}(.*)$").unwrap();
-            let caps = if let Some(caps) = re.captures(&fmt_str) {
-                caps
-            } else {
-                return syn::Error::new(*fmt_span, "Invalid multicase format")
-                    .to_compile_error()
-                    .into();
-            };
-
-            let fmt_str = format!(
-                "{}{{{}}}{}",
-                &caps[1],
-                caps.get(3).map(|r| r.as_str()).unwrap_or(""),
-                &caps[4]
-            );
-
-            let mut fmt_arg: syn::Expr = match syn::parse_str(&caps[2]) {
-                Ok(fmt_arg) => fmt_arg,
-                Err(err) => {
-                    return syn::Error::new(*fmt_span, format!("{}: `{}`", err, &caps[2]))
-                        .to_compile_error()
-                        .into();
-                }
-            };
-
-            let case_id: syn::Ident = parse_quote! { case_id };
-
-            VarRewriter {
-                case_id: case_id.clone(),
-            }
-            .visit_expr_mut(&mut fmt_arg);
-
+            (quote! {}, quote! {})
+        };
+        let (start_bench, report_bench) = if attr.bench {
             (
-                case_id,
                 quote! {
-                    print!(#fmt_str, #fmt_arg);
+                    let __argio_bench = __argio::timing::Bench::start();
+                },
+                quote! {
+                    __argio_bench.report();
                 },
             )
+        } else {
+            (quote! {}, quote! {})
+        };
+        let parse_options = if args.iter().any(|arg| arg.attr.arg) {
+            let names = args
+                .iter()
+                .filter(|arg| arg.attr.arg)
+                .map(|arg| option_name(arg.ident()));
+            quote! {
+                __argio::args::parse(&[#(#names),*]);
+            }
+        } else {
+            quote! {}
+        };
+        let init = quote! {
+            #start_deadline
+            #start_bench
+            #parse_options
+            #[allow(unused_macros)]
+            macro_rules! print {
+                ($($t:tt)*) => {{
+                    __argio::io::print(format_args!($($t)*));
+                    #flush_print
+                }};
+            }
+            #[allow(unused_macros)]
+            macro_rules! println {
+                () => {{
+                    __argio::io::println(format_args!(""));
+                    #flush_print
+                }};
+                ($($t:tt)*) => {{
+                    __argio::io::println(format_args!($($t)*));
+                    #flush_print
+                }};
+            }
+            #[allow(unused_macros)]
+            macro_rules! flush {
+                () => {
+                    __argio::io::flush()
+                };
+            }
+            #buffer_output
+            #init_source
+            #mirror
+            #start_stats
+            #interactive_input
+            #body_input
+            #watch_deadline
         };
 
-        quote! {
-            #vis fn #name() {
-                #input_macro ! {
-                    cases: usize,
-                }
+        let finish_source = match &input.source {
+            Some(source) if attr.strict_input => quote! {
+                #source.finish();
+            },
+            _ => quote! {},
+        };
 
-                for #case_id in 0..cases {
-                    #print_header
+        // The interaction of local runs is reported in debug builds.
+        let report_session = if attr.interactive {
+            quote! {
+                if cfg!(debug_assertions) {
+                    __argio::io::report_session();
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let flush_end = match &attr.record {
+            Some(dir) => {
+                let dir = manifest_path(&dir.value()).to_string_lossy().into_owned();
+                quote! {
+                    __argio::io::flush();
+                    __argio::io::save_record(#dir);
+                    #report_stats
+                    #report_bench
+                }
+            }
+            None => quote! {
+                __argio::io::flush();
+                #report_stats
+                #report_session
+                #report_bench
+            },
+        };
 
-                    let #ret_var = (|| -> #ret_type {
-                        #input_macro ! {
-                            #args
-                        }
-                        #body
-                    })();
+        Setup {
+            init,
+            flush_end,
+            finish_source,
+        }
+    }
+}
 
-                    #print_code
-                }
+// Opens the source of the input, and the output file with `files`.
+fn source_code(
+    attr: &ArgioAttr,
+    input: &Input,
+    capacity: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    // The input and the output of local runs are recorded in debug builds.
+    let start_record = if attr.record.is_some() {
+        quote! {
+            if cfg!(debug_assertions) {
+                __argio::io::record();
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let source = match &input.source {
+        Some(source) => source,
+        None => {
+            return quote! {
+                __argio::diag::install();
+                #start_record
             }
         }
+    };
+    let reader = if attr.files {
+        quote! { __argio::io::open(&__argio_input) }
+    } else if attr.input_file_arg {
+        quote! { __argio::io::arg_reader() }
+    } else {
+        quote! { __argio::io::reader() }
+    };
+    let mut scanner = if attr.byte_input {
+        match &attr.input_str {
+            Some(input_str) => quote! {
+                __argio::scan::ByteScanner::new(__argio::io::str_reader(#input_str))
+            },
+            None => quote! { __argio::scan::ByteScanner::new(#reader) },
+        }
+    } else if let Some(input_str) = &attr.input_str {
+        quote! { __argio::scan::Scanner::new(__argio::io::str_reader(#input_str)) }
+    } else if attr.fast_input {
+        quote! { __argio::scan::Scanner::once(#reader) }
+    } else {
+        quote! { __argio::scan::Scanner::new(#reader) }
+    };
+    if attr.normalize_input {
+        scanner = quote! { #scanner.normalize() };
+    }
+    let (open_files, create_output) = if attr.files {
+        (
+            quote! {
+                let (__argio_input, __argio_output) = __argio::io::file_paths();
+                __argio::diag::message(
+                    __argio::diag::Level::Info,
+                    format_args!(
+                        "reading `{}`, writing `{}`",
+                        __argio_input.display(),
+                        __argio_output.display()
+                    ),
+                );
+            },
+            quote! {
+                __argio::io::create(&__argio_output, #capacity);
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+    quote! {
+        __argio::diag::install();
+        #open_files
+        #start_record
+        let mut #source = #scanner.report();
+        #create_output
+    }
+}
+
+// Sets up the output, which is connected to the judge with `interactive`, and buffered as given
+// by `flush` otherwise.
+fn output_code(attr: &ArgioAttr, capacity: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if attr.interactive {
+        let addr = match &attr.connect {
+            Some(addr) => quote! { ::core::option::Option::Some(#addr) },
+            None => quote! { ::core::option::Option::None },
+        };
+        quote! {
+            __argio::io::connect(#addr);
+            __argio::io::start_session();
+        }
+    } else if attr.files {
+        quote! {}
     } else {
+        let path = match &attr.output_file {
+            Some(path) => quote! { ::core::option::Option::Some(#path) },
+            None => quote! { ::core::option::Option::None },
+        };
+        let buffer = match attr.flush {
+            Flush::Never => quote! {},
+            Flush::Once => quote! {
+                __argio::io::accumulate(#capacity);
+            },
+            Flush::PerCase | Flush::AtEnd => quote! {
+                __argio::io::buffer(#capacity);
+            },
+        };
         quote! {
-            #vis fn #name() {
-                let #ret_var = (|| -> #ret_type {
-                    #input_macro ! {
-                        #args
-                    }
-                    #body
-                })();
+            __argio::io::redirect(#path);
+            #buffer
+        }
+    }
+}
 
-                #print_code
+// In interactive mode, the output is flushed on each print, the body can read more input with
+// `input!`, and `expect!` checks the responses of the judge.
+fn interactive_code(
+    attr: &ArgioAttr,
+    input: &Input,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if !attr.interactive || input.source.is_none() {
+        return (quote! {}, quote! {});
+    }
+    let read = input.read(quote! { $($t)* });
+    let read_response = input.read(quote! { __argio_response: String });
+    (
+        quote! { __argio::io::flush(); },
+        quote! {
+            #[allow(unused_macros)]
+            macro_rules! input {
+                ($($t:tt)*) => {
+                    #read
+                };
+            }
+            #[allow(unused_macros)]
+            macro_rules! expect {
+                ($($p:pat)|+) => {{
+                    #read_response
+                    if !matches!(__argio_response.as_str(), $($p)|+) {
+                        __argio::io::unexpected(
+                            &__argio_response,
+                            stringify!($($p)|+),
+                        );
+                    }
+                    __argio_response
+                }};
             }
+        },
+    )
+}
+
+// The body can read more lines and values from the same source at any point.
+fn body_input_code(input: &Input) -> proc_macro2::TokenStream {
+    let source = match &input.source {
+        Some(source) => source,
+        None => return quote! {},
+    };
+    let read = input.read(quote! { __argio_value: $($t)+ });
+    quote! {
+        #[allow(unused_macros)]
+        macro_rules! readline {
+            () => {
+                __argio::scan::Source::next_line(&mut #source).map(String::from)
+            };
+        }
+        #[allow(unused_macros)]
+        macro_rules! read {
+            ($($t:tt)+) => {{
+                #read
+                __argio_value
+            }};
         }
+    }
+}
+
+// Measures the time of each case with `timing`, returning the code to start the measurement, to
+// start a case and to report the times.
+fn timing_code(
+    attr: &ArgioAttr,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    if attr.timing {
+        (
+            quote! {
+                let __argio_timing = __argio::timing::Timing::new();
+            },
+            quote! {
+                let __argio_start = __argio_timing.start();
+            },
+            quote! {
+                __argio_timing.report();
+            },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {})
+    }
+}
+
+fn record_timing(attr: &ArgioAttr, case: Option<&syn::Ident>) -> proc_macro2::TokenStream {
+    if !attr.timing {
+        return quote! {};
+    }
+    let case = match case {
+        Some(case) => quote! { Some(#case) },
+        None => quote! { None },
     };
-    ret.into()
+    quote! {
+        __argio_timing.record(#case, __argio_start);
+    }
 }
 
-struct VarRewriter {
+// The pieces of the generated function shared by the code for the cases.
+struct Codegen<'a> {
+    attr: &'a ArgioAttr,
+    input: Input,
+    args: &'a [Param],
+    global_args: Vec<&'a Param>,
+    case_args: Vec<&'a Param>,
+    read_args: proc_macro2::TokenStream,
+    body: proc_macro2::TokenStream,
+    ret_type: syn::Type,
+    ret_var: syn::Ident,
+    wrapped: syn::Expr,
+    print_code: proc_macro2::TokenStream,
     case_id: syn::Ident,
+    cases: syn::Ident,
+    set_id: syn::Ident,
+    sets: syn::Ident,
 }
 
-impl syn::visit_mut::VisitMut for VarRewriter {
-    fn visit_ident_mut(&mut self, i: &mut syn::Ident) {
-        if i == "i" {
-            *i = self.case_id.clone();
+// The loop over the cases or the sets, with the code reading their number before it, and the code
+// breaking it at the end of the input in it.
+struct CaseLoop {
+    read: proc_macro2::TokenStream,
+    head: proc_macro2::TokenStream,
+    check_eof: proc_macro2::TokenStream,
+}
+
+impl Codegen<'_> {
+    // Solves the only case of the input.
+    fn single(
+        &self,
+        setup: &Setup,
+        read_options: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let Setup {
+            init,
+            flush_end,
+            finish_source,
+        } = setup;
+        let Codegen {
+            read_args,
+            body,
+            ret_type,
+            ret_var,
+            print_code,
+            ..
+        } = self;
+        let (init_timing, start_timing, _) = timing_code(self.attr);
+        let record_timing = record_timing(self.attr, None);
+        quote! {
+            #init
+            #init_timing
+            #start_timing
+            let #ret_var = (|| -> #ret_type {
+                #read_options #read_args
+                #body
+            })();
+            #record_timing
+
+            #print_code
+            #flush_end
+            #finish_source
         }
     }
-}
 
-struct ArgioAttr {
-    multicase: Option<(String, proc_macro2::Span)>,
-    input: Option<syn::Path>,
-    output: Option<syn::Path>,
-}
+    // Solves the cases of `multicase`, which are grouped into the sets with `sets`.
+    fn multicase(
+        &self,
+        multicase: &Multicase,
+        setup: &Setup,
+        read_options: proc_macro2::TokenStream,
+    ) -> syn::Result<proc_macro2::TokenStream> {
+        let attr = self.attr;
+        let Codegen { case_id, cases, .. } = self;
+        let case_no: syn::Ident = parse_quote! { __argio_case_no };
+        let (print_header, print_code, header_macro) = self.case_header(multicase, &case_no)?;
 
-impl syn::parse::Parse for ArgioAttr {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let mut ret = ArgioAttr {
-            multicase: None,
-            input: None,
-            output: None,
+        // The cases not selected are skipped, so the separator is not printed before the first
+        // case selected.
+        let print_separator = match &multicase.case_separator {
+            Some(sep) => quote! {
+                if #case_id > __argio::io::first_selected_case() {
+                    print!("{}", #sep);
+                }
+            },
+            None => quote! {},
         };
+        let case_loop = case_loop(multicase, &self.input, case_id, cases, "cases");
+        let bind_vars = self.bind_vars(multicase, &case_no, header_macro);
 
-        let mut first = true;
-
-        loop {
-            if first {
-                first = false;
+        let read_first = multicase.read_all.is_some() || multicase.parallel;
+        let (init_progress, progress_done, finish_progress) = if attr.progress {
+            let total = if multicase.eof && !read_first {
+                quote! { None }
             } else {
-                if !input.peek(Token![,]) {
-                    break;
-                }
-                input.parse::<Token![,]>()?;
+                quote! { Some(#cases) }
             };
+            (
+                quote! {
+                    let __argio_progress = __argio::progress::Progress::new(#total);
+                },
+                quote! {
+                    __argio_progress.done();
+                },
+                quote! {
+                    __argio_progress.finish();
+                },
+            )
+        } else {
+            (quote! {}, quote! {}, quote! {})
+        };
 
-            if !input.peek(syn::Ident) {
-                break;
+        let read_case = if read_first {
+            quote! {}
+        } else {
+            self.read_args.clone()
+        };
+        let (init_timing, start_timing, report_timing) = timing_code(attr);
+        let record_timing = record_timing(attr, Some(case_id));
+        let run_case = self.run_case(multicase, print_separator, print_header, bind_vars);
+        let flush_case = if attr.flush == Flush::PerCase {
+            quote! {
+                __argio::io::flush();
             }
-            let var = input.parse::<syn::Ident>()?;
+        } else {
+            quote! {}
+        };
+        let solve_case = quote! {
+            #read_case
+            if __argio::io::selected_case(#case_id) {
+                #[allow(unused_variables)]
+                let #case_no: usize = #case_id + 1;
+                #start_timing
+                #run_case
+                #record_timing
 
-            if var == "multicase" {
-                if input.peek(Token![=]) {
-                    input.parse::<Token![=]>()?;
-                    let s = input.parse::<syn::LitStr>()?;
-                    ret.multicase = Some((s.value(), s.span()));
-                } else {
-                    ret.multicase = Some(("Case #{i+1}: ".to_string(), input.span()));
+                #print_code
+                #flush_case
+                #progress_done
+            }
+        };
+
+        let init_cases = quote! {
+            #init_timing
+            #init_progress
+        };
+        let finish_cases = quote! {
+            #finish_progress
+            #report_timing
+        };
+        let solve_cases = if read_first {
+            self.solve_read_cases(multicase, case_loop, solve_case, init_cases, finish_cases)
+        } else {
+            let CaseLoop {
+                read,
+                head,
+                check_eof,
+            } = case_loop;
+            quote! {
+                #read
+
+                #init_cases
+                #head {
+                    #check_eof
+                    #solve_case
                 }
-            } else if var == "output" {
-                input.parse::<Token![=]>()?;
-                let path = input.parse::<syn::Path>()?;
-                ret.output = Some(path);
-            } else if var == "input" {
-                input.parse::<Token![=]>()?;
-                let path = input.parse::<syn::Path>()?;
-                ret.input = Some(path);
-            } else {
-                return Err(syn::Error::new(
-                    var.span(),
-                    format!("argio: invalid attr: {}", var),
-                ));
+                #finish_cases
             }
+        };
+
+        let solve_all = match &attr.sets {
+            Some(sets) => self.sets(sets, solve_cases)?,
+            None => solve_cases,
+        };
+
+        let Setup {
+            init,
+            flush_end,
+            finish_source,
+        } = setup;
+        let read_globals = read_args(&self.input, None, &self.global_args);
+        Ok(quote! {
+            #init
+            #read_options #read_globals
+            #solve_all
+            #flush_end
+            #finish_source
+        })
+    }
+
+    // Prints the header of each case, or the answer with it with `case_format`. With
+    // `header = manual`, the header is printed by `case_header!()` in the body instead.
+    fn case_header(
+        &self,
+        multicase: &Multicase,
+        case_no: &syn::Ident,
+    ) -> syn::Result<(
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+    )> {
+        let Codegen {
+            ret_var, wrapped, ..
+        } = self;
+        let mut rewriter = VarRewriter::new(multicase, &self.case_id, case_no, &self.cases);
+        if let Some(sets) = &self.attr.sets {
+            let mut outer = VarRewriter { renames: vec![] };
+            outer
+                .renames
+                .push((parse_quote! { sets }, self.sets.clone()));
+            outer.add_names(sets, &self.set_id, &self.sets);
+            rewriter.renames.splice(0..0, outer.renames);
+        }
+
+        let (print_header, print_code) = if let Some((fmt, fmt_span)) = &multicase.case_format {
+            rewriter
+                .renames
+                .push((parse_quote! { ret }, ret_var.clone()));
+            let (fmt_str, args) = header_args(fmt, *fmt_span, &mut rewriter)?;
+            let print_code = quote! {
+                let #ret_var = #wrapped;
+                println!(#fmt_str #(, #args)*);
+            };
+            (quote! {}, print_code)
+        } else {
+            let (fmt, fmt_span) = &multicase.format;
+            let (fmt_str, args) = header_args(fmt, *fmt_span, &mut rewriter)?;
+            let print_header = quote! {
+                print!(#fmt_str #(, #args)*);
+            };
+            (print_header, self.print_code.clone())
+        };
+
+        if multicase.manual_header {
+            let header_macro = quote! {
+                #[allow(unused_macros)]
+                macro_rules! case_header {
+                    () => {
+                        #print_header
+                    };
+                }
+            };
+            Ok((quote! {}, print_code, header_macro))
+        } else {
+            Ok((print_header, print_code, quote! {}))
+        }
+    }
+
+    // Binds the variables of the cases and the sets given by `cases_var` and `case_index`.
+    fn bind_vars(
+        &self,
+        multicase: &Multicase,
+        case_no: &syn::Ident,
+        header_macro: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let Codegen {
+            case_id,
+            cases,
+            set_id,
+            sets: sets_ident,
+            ..
+        } = self;
+        let mut bind_vars = quote! {};
+        if let Some(cases_var) = &multicase.cases_var {
+            bind_vars.extend(quote! {
+                let #cases_var: usize = #cases;
+            });
+        }
+        if let Some(case_index) = &multicase.case_index {
+            bind_vars.extend(quote! {
+                let #case_index: usize = #case_id;
+            });
+            let case_param = self
+                .args
+                .iter()
+                .any(|arg| arg.vars().iter().any(|var| var.ident == "case"));
+            if !case_param {
+                bind_vars.extend(quote! {
+                    let case: usize = #case_no;
+                });
+            }
+        }
+        if let Some(sets) = &self.attr.sets {
+            if let Some(cases_var) = &sets.cases_var {
+                bind_vars.extend(quote! {
+                    let #cases_var: usize = #sets_ident;
+                });
+            }
+            if let Some(case_index) = &sets.case_index {
+                bind_vars.extend(quote! {
+                    let #case_index: usize = #set_id;
+                });
+            }
+        }
+        bind_vars.extend(header_macro);
+        bind_vars
+    }
+
+    // Runs the body for a case, on a thread of its own with `case_timeout`.
+    fn run_case(
+        &self,
+        multicase: &Multicase,
+        print_separator: proc_macro2::TokenStream,
+        print_header: proc_macro2::TokenStream,
+        bind_vars: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let Codegen {
+            case_id,
+            body,
+            ret_type,
+            ret_var,
+            ..
+        } = self;
+        let millis = match multicase.case_timeout {
+            Some(millis) => millis,
+            None => {
+                return quote! {
+                    #print_separator
+                    #print_header
+                    let #ret_var = (|| -> #ret_type {
+                        #bind_vars
+                        #body
+                    })();
+                }
+            }
+        };
+        let fallback = multicase.fallback.as_ref().unwrap();
+        let first_line_vars = multicase
+            .first_line
+            .iter()
+            .flatten()
+            .flat_map(|arg| Param::pat_vars(&arg.pat));
+        let clone_globals = self
+            .global_args
+            .iter()
+            .flat_map(|arg| arg.vars())
+            .chain(first_line_vars)
+            .map(|var| {
+                let mutability = &var.mutability;
+                let ident = &var.ident;
+                quote! {
+                    let #mutability #ident = #ident.clone();
+                }
+            });
+        quote! {
+            #print_separator
+            #print_header
+            let #ret_var = {
+                #(#clone_globals)*
+                let __argio_case = move || -> #ret_type {
+                    #bind_vars
+                    #body
+                };
+                match __argio::io::spawn_with_timeout(
+                    std::time::Duration::from_millis(#millis),
+                    __argio_case,
+                ) {
+                    Ok(ret) => ret,
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                        __argio::diag::message(
+                            __argio::diag::Level::Warning,
+                            format_args!("case #{} timed out", #case_id + 1),
+                        );
+                        #fallback
+                    }
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                        panic!("argio: case #{} panicked", #case_id + 1);
+                    }
+                }
+            };
+        }
+    }
+
+    // Reads all the cases before solving them with `read_all` or `parallel`, which solves them on
+    // the threads of `rayon` and prints their outputs in order.
+    fn solve_read_cases(
+        &self,
+        multicase: &Multicase,
+        case_loop: CaseLoop,
+        solve_case: proc_macro2::TokenStream,
+        init_cases: proc_macro2::TokenStream,
+        finish_cases: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let Codegen {
+            case_id,
+            cases,
+            read_args,
+            ..
+        } = self;
+        let vars = self
+            .case_args
+            .iter()
+            .flat_map(|arg| arg.vars())
+            .collect::<Vec<_>>();
+        let names = vars.iter().map(|var| &var.ident).collect::<Vec<_>>();
+        let pats = vars.iter().map(|var| {
+            let mutability = &var.mutability;
+            let ident = &var.ident;
+            quote! { #mutability #ident }
+        });
+
+        let all_cases = multicase.read_all.clone().flatten();
+        let bind_all = match &all_cases {
+            Some(all_cases) => quote! {
+                let #all_cases = &__argio_inputs[..];
+            },
+            None => quote! {},
+        };
+
+        let solve_cases = if multicase.parallel {
+            let cases_iter = if all_cases.is_some() {
+                quote! {
+                    __argio::rayon::iter::ParallelIterator::cloned(
+                        __argio::rayon::iter::IntoParallelRefIterator::par_iter(&__argio_inputs),
+                    )
+                }
+            } else {
+                quote! { __argio::rayon::iter::IntoParallelIterator::into_par_iter(__argio_inputs) }
+            };
+            quote! {
+                let __argio_outputs: Vec<String> = __argio::rayon::iter::ParallelIterator::collect(
+                    __argio::rayon::iter::ParallelIterator::map(
+                        __argio::rayon::iter::IndexedParallelIterator::enumerate(#cases_iter),
+                        |(#case_id, (#(#pats,)*))| {
+                            __argio::io::buffered(|| {
+                                #solve_case
+                            }).1
+                        },
+                    ),
+                );
+                for __argio_output in __argio_outputs {
+                    print!("{}", __argio_output);
+                }
+            }
+        } else {
+            let cases_iter = if all_cases.is_some() {
+                quote! { __argio_inputs.iter().cloned().enumerate() }
+            } else {
+                quote! { __argio_inputs.into_iter().enumerate() }
+            };
+            quote! {
+                for (#case_id, (#(#pats,)*)) in #cases_iter {
+                    #solve_case
+                }
+            }
+        };
+
+        let CaseLoop {
+            read,
+            head,
+            check_eof,
+        } = case_loop;
+        quote! {
+            #read
+
+            let mut __argio_inputs = vec![];
+            #head {
+                #check_eof
+                #read_args
+                __argio_inputs.push((#(#names,)*));
+            }
+
+            #[allow(unused_variables)]
+            let #cases: usize = __argio_inputs.len();
+            #bind_all
+
+            #init_cases
+            #solve_cases
+            #finish_cases
+        }
+    }
+
+    // Solves the cases in each set of `sets`, printing the header of the set before them.
+    fn sets(
+        &self,
+        sets: &Multicase,
+        solve_cases: proc_macro2::TokenStream,
+    ) -> syn::Result<proc_macro2::TokenStream> {
+        let Codegen {
+            set_id,
+            sets: sets_ident,
+            ..
+        } = self;
+        let set_no: syn::Ident = parse_quote! { __argio_set_no };
+        let mut rewriter = VarRewriter::new(sets, set_id, &set_no, sets_ident);
+        rewriter
+            .renames
+            .push((parse_quote! { sets }, sets_ident.clone()));
+        let (fmt, fmt_span) = &sets.format;
+        let (fmt_str, args) = header_args(fmt, *fmt_span, &mut rewriter)?;
+        let print_separator = match &sets.case_separator {
+            Some(sep) => quote! {
+                if #set_id > 0 {
+                    print!("{}", #sep);
+                }
+            },
+            None => quote! {},
+        };
+        let CaseLoop {
+            read,
+            head,
+            check_eof,
+        } = case_loop(sets, &self.input, set_id, sets_ident, "sets");
+
+        Ok(quote! {
+            #read
+            #head {
+                #check_eof
+                #[allow(unused_variables)]
+                let #set_no: usize = #set_id + 1;
+                #print_separator
+                print!(#fmt_str #(, #args)*);
+                #solve_cases
+            }
+        })
+    }
+}
+
+// Loops over the cases or the sets, whose number is given by `count`, the first line of the
+// input, or the end of the input with `eof`.
+fn case_loop(
+    multicase: &Multicase,
+    input: &Input,
+    id: &syn::Ident,
+    count: &syn::Ident,
+    label: &str,
+) -> CaseLoop {
+    if let Some(expr) = &multicase.count {
+        CaseLoop {
+            read: quote! {
+                let #count: usize = #expr;
+            },
+            head: quote! { for #id in 0..#count },
+            check_eof: quote! {},
+        }
+    } else if multicase.eof {
+        let source = input.source.as_ref().unwrap();
+        CaseLoop {
+            read: quote! {},
+            head: quote! { for #id in 0.. },
+            check_eof: quote! {
+                if __argio::scan::Source::is_empty(&mut #source) {
+                    break;
+                }
+            },
+        }
+    } else if let Some(first_line) = &multicase.first_line {
+        let read = first_line
+            .iter()
+            .map(|var| input.read_arg(None, var, quote! {}))
+            .collect::<proc_macro2::TokenStream>();
+        let first = match first_line[0].pat.as_ref() {
+            syn::Pat::Ident(pat) => &pat.ident,
+            _ => unreachable!(),
+        };
+        CaseLoop {
+            read: quote! {
+                #read
+                let #count: usize = #first;
+            },
+            head: quote! { for #id in 0..#count },
+            check_eof: quote! {},
+        }
+    } else {
+        CaseLoop {
+            read: diagnose(label, None, input.read(quote! { #count: usize })),
+            head: quote! { for #id in 0..#count },
+            check_eof: quote! {},
+        }
+    }
+}
+
+// The function replacing the original one, which reads the input, runs the body and prints the
+// output. `run` calls it in the tests.
+struct MainFn {
+    entry_fn: proc_macro2::TokenStream,
+    fn_attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    name: syn::Ident,
+    main_ret: proc_macro2::TokenStream,
+    body: proc_macro2::TokenStream,
+    run: proc_macro2::TokenStream,
+}
+
+impl MainFn {
+    // Expands the function with `argio` at `krate`, which is `crate::argio` in the file written
+    // by `bundle`, along with `runner` and `wasm`.
+    fn expand(&self, attr: &ArgioAttr, krate: &syn::Path) -> proc_macro2::TokenStream {
+        let MainFn {
+            entry_fn,
+            fn_attrs,
+            vis,
+            name,
+            main_ret,
+            body,
+            run,
+        } = self;
+        let runner_fn = if let Some(runner) = &attr.runner {
+            quote! {
+                #vis fn #runner(input: &str) -> String {
+                    use #krate as __argio;
+                    __argio::io::run(input, #run)
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let wasm_fn = if let Some(wasm) = &attr.wasm {
+            quote! {
+                #[cfg(target_arch = "wasm32")]
+                #[#krate::wasm_bindgen::prelude::wasm_bindgen(wasm_bindgen = #krate::wasm_bindgen)]
+                pub fn #wasm(input: &str) -> String {
+                    use #krate as __argio;
+                    __argio::io::run(input, #run)
+                }
+            }
+        } else {
+            quote! {}
+        };
+        quote! {
+            #entry_fn
+            #(#fn_attrs)*
+            #vis fn #name() #main_ret {
+                #[allow(unused_imports)]
+                use #krate as __argio;
+                #body
+            }
+            #runner_fn
+            #wasm_fn
+        }
+    }
+}
+
+// Makes the tests of the function: the samples given by `argio::test`, the files and the
+// suites, and the tests of `argio::stress`, `argio::property`, `judge` and `record`.
+fn test_fns(
+    attr: &ArgioAttr,
+    fn_name: &syn::Ident,
+    mut tests: Vec<SampleTest>,
+    stress: Option<Stress>,
+    property: Option<(Property, proc_macro2::TokenStream)>,
+    run: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let krate = &attr.krate;
+    // The memory usage of each run in the tests is checked by the allocator counting it.
+    let (run, count_alloc) = match attr.memory_limit {
+        Some(limit) => (
+            quote! { || __argio::memory::check(#limit, #run) },
+            quote! {
+                #[cfg(test)]
+                #[global_allocator]
+                static __ARGIO_ALLOC: #krate::memory::Counter = #krate::memory::Counter;
+            },
+        ),
+        None => (run.clone(), quote! {}),
+    };
+    let compete = match &attr.compete {
+        Some(path) => Some(compete_suite(path.as_ref(), fn_name)?),
+        None => None,
+    };
+    let compete_tolerance = compete.as_ref().and_then(|suite| suite.tolerance);
+    // The time limit of the problem is checked in the same way as the memory.
+    let (run, track_suite) = match compete {
+        Some(suite) => {
+            tests.extend(suite.tests);
+            let run = match suite.time_limit {
+                Some(limit) => quote! { || __argio::timing::check(#limit, #run) },
+                None => run,
+            };
+            let path = suite.path;
+            (
+                run,
+                quote! {
+                    #[cfg(test)]
+                    const _: &str = include_str!(#path);
+                },
+            )
+        }
+        None => (run, quote! {}),
+    };
+    let float_tolerance = match (&attr.float_tolerance, compete_tolerance) {
+        (Some(tolerance), _) => Some(tolerance.clone()),
+        (None, Some(tolerance)) => Some(parse_quote! { #tolerance }),
+        (None, None) => None,
+    };
+    // `oj download` saves the samples as `test/sample-1.in`, `test/sample-1.out` and so on.
+    let oj_pattern = attr
+        .oj
+        .as_ref()
+        .map(|dir| syn::LitStr::new(&format!("{}/*.in", dir.value()), dir.span()));
+    if let Some(pattern) = attr.tests.as_ref().or(oj_pattern.as_ref()) {
+        tests.extend(sample_files(pattern, fn_name)?);
+    }
+    let accept = match (&attr.checker, &float_tolerance) {
+        (Some(checker), _) => quote! { #checker(input, expected, actual) },
+        (None, Some(tolerance)) => quote! {
+            __argio::testing::compare_float(expected, actual, #tolerance).is_ok()
+        },
+        (None, None) => quote! { __argio::testing::compare(expected, actual).is_ok() },
+    };
+    let accept = quote! { |input: &str, expected: &str, actual: &str| #accept };
+    // Returns the path of the snapshot file `name` in the directory given by `snapshot`.
+    let snapshot_path = |name: String, span: proc_macro2::Span, what: &str| match &attr.snapshot {
+        Some(dir) => Ok(manifest_path(&dir.value())
+            .join(name)
+            .to_string_lossy()
+            .into_owned()),
+        None => Err(syn::Error::new(
+            span,
+            format!("argio: {}, or snapshot in #[argio]", what),
+        )),
+    };
+
+    let mut test_fns = vec![];
+    for (i, test) in tests.iter().enumerate() {
+        let test_name = match &test.name {
+            Some(name) => name.clone(),
+            None => quote::format_ident!("{}_sample_{}", fn_name, i + 1),
+        };
+        let input = &test.input;
+        let expected = match &test.expected {
+            Some(Expected::Value(expected)) => expected,
+            Some(Expected::Snapshot(path)) => {
+                test_fns.push(snapshot_test(&test_name, krate, input, path, &run, &accept));
+                continue;
+            }
+            None => {
+                let path = snapshot_path(
+                    format!("{}.out", test_name),
+                    input.span(),
+                    "argio::test requires expected",
+                )?;
+                test_fns.push(snapshot_test(
+                    &test_name, krate, input, &path, &run, &accept,
+                ));
+                continue;
+            }
+        };
+        let check = match (&attr.checker, &float_tolerance) {
+            (Some(checker), _) => quote! {
+                let accepted: bool = #checker(#input, #expected, &actual);
+                __argio::testing::check_with(#input, #expected, &actual, accepted);
+            },
+            (None, Some(tolerance)) => quote! {
+                __argio::testing::check_float(#input, #expected, &actual, #tolerance);
+            },
+            (None, None) => quote! {
+                __argio::testing::check(#input, #expected, &actual);
+            },
+        };
+        test_fns.push(quote! {
+            #[cfg(test)]
+            #[::core::prelude::v1::test]
+            fn #test_name() {
+                use #krate as __argio;
+                let actual = __argio::io::run(#input, #run);
+                #check
+            }
+        });
+    }
+    let judge_fn = attr.judge.as_ref().map(|judge| {
+        let test_name = quote::format_ident!("{}_judge", fn_name);
+        quote! {
+            #[cfg(test)]
+            #[::core::prelude::v1::test]
+            fn #test_name() {
+                use #krate as __argio;
+                __argio::testing::interact(#run, #judge);
+            }
+        }
+    });
+    let stress_fn = match stress {
+        Some(stress) => {
+            let test_name = quote::format_ident!("{}_stress", fn_name);
+            let Stress { gen, brute, iters } = stress;
+            let iters = iters.unwrap_or_else(|| parse_quote! { 100 });
+            let run_stress = match brute {
+                Some(brute) => quote! {
+                    __argio::testing::stress(
+                        #iters,
+                        #gen,
+                        |input| __argio::io::run(input, #run),
+                        |input| __argio::io::run(input, #brute),
+                        #accept,
+                    );
+                },
+                None => {
+                    let path = snapshot_path(
+                        format!("{}.out", test_name),
+                        gen.span(),
+                        "argio::stress requires brute",
+                    )?;
+                    quote! {
+                        __argio::testing::stress_snapshot(
+                            #path,
+                            #iters,
+                            #gen,
+                            |input| __argio::io::run(input, #run),
+                            #accept,
+                        );
+                    }
+                }
+            };
+            Some(quote! {
+                #[cfg(test)]
+                #[::core::prelude::v1::test]
+                fn #test_name() {
+                    use #krate as __argio;
+                    #run_stress
+                }
+            })
+        }
+        None => None,
+    };
+    let property_fn = property.map(|(property, gen)| {
+        let test_name = quote::format_ident!("{}_property", fn_name);
+        let Property { check, iters } = property;
+        let iters = iters.unwrap_or_else(|| parse_quote! { 100 });
+        quote! {
+            #[cfg(test)]
+            #[::core::prelude::v1::test]
+            fn #test_name() {
+                use #krate as __argio;
+                __argio::testing::property(
+                    #iters,
+                    #gen,
+                    |input| __argio::io::run(input, #run),
+                    #check,
+                );
+            }
+        }
+    });
+    let replay_fn = attr.record.as_ref().map(|dir| {
+        let test_name = quote::format_ident!("{}_recorded", fn_name);
+        let dir = manifest_path(&dir.value()).to_string_lossy().into_owned();
+        quote! {
+            #[cfg(test)]
+            #[::core::prelude::v1::test]
+            fn #test_name() {
+                use #krate as __argio;
+                __argio::testing::replay(#dir, |input| __argio::io::run(input, #run), #accept);
+            }
+        }
+    });
+    Ok(quote! {
+        #(#test_fns)*
+        #replay_fn
+        #stress_fn
+        #property_fn
+        #judge_fn
+        #count_alloc
+        #track_suite
+    })
+}
+
+// Makes a test comparing the output for `input` with the snapshot file at `path`.
+fn snapshot_test(
+    test_name: &syn::Ident,
+    krate: &syn::Path,
+    input: &syn::Expr,
+    path: &str,
+    run: &proc_macro2::TokenStream,
+    accept: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote! {
+        #[cfg(test)]
+        #[::core::prelude::v1::test]
+        fn #test_name() {
+            use #krate as __argio;
+            let actual = __argio::io::run(#input, #run);
+            __argio::testing::check_snapshot(#path, #input, &actual, #accept);
+        }
+    }
+}
+
+/// Adds a test running the program on a sample input
+///
+/// This is used together with `#[argio]`, and the attrs are collected by it.
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    companion("test", attr, item)
+}
+
+/// Adds a test comparing the program with a brute force solution on random inputs
+///
+/// This is used together with `#[argio]`, and the attrs are collected by it.
+#[proc_macro_attribute]
+pub fn stress(attr: TokenStream, item: TokenStream) -> TokenStream {
+    companion("stress", attr, item)
+}
+
+/// Adds a test checking a property of the output on random inputs made from the parameters
+///
+/// This is used together with `#[argio]`, and the attrs are collected by it.
+#[proc_macro_attribute]
+pub fn property(attr: TokenStream, item: TokenStream) -> TokenStream {
+    companion("property", attr, item)
+}
+
+/// Makes a generator of random inputs from a function taking a random number generator
+///
+/// A function named `main` becomes the main function of a program printing an input.
+/// Other functions are kept as they are, so that they can be used by `argio::stress`.
+#[proc_macro_attribute]
+pub fn generator(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let Generator { krate } = parse_macro_input!(attr as Generator);
+    let mut item = parse_macro_input!(item as syn::ItemFn);
+
+    if item.sig.inputs.len() != 1 {
+        return syn::Error::new_spanned(
+            &item.sig.inputs,
+            "argio: a generator must take only a random number generator (`&mut argio::random::Rng`)",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    if item.sig.ident != "main" {
+        return quote! {
+            #[allow(dead_code)]
+            #item
+        }
+        .into();
+    }
+
+    let vis = item.vis.clone();
+    item.sig.ident = parse_quote! { __argio_gen };
+    quote! {
+        #vis fn main() {
+            use #krate as __argio;
+            #item
+            let mut rng = __argio::random::Rng::new(__argio::random::seed());
+            let input = __argio_gen(&mut rng).to_string();
+            if input.ends_with('\n') {
+                print!("{}", input);
+            } else {
+                println!("{}", input);
+            }
+        }
+    }
+    .into()
+}
+
+fn companion(name: &str, attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    let mut item = parse_macro_input!(item as syn::ItemFn);
+    let ident = quote::format_ident!("{}", name);
+
+    // Move this attr below `#[argio]`, so that it is collected by `#[argio]`.
+    let pos = match item.attrs.iter().position(|attr| {
+        attr.path
+            .segments
+            .last()
+            .is_some_and(|seg| seg.ident == "argio")
+    }) {
+        Some(pos) => pos,
+        None => {
+            return syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!("argio: argio::{} must be used with #[argio]", name),
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+    item.attrs
+        .insert(pos + 1, parse_quote! { #[argio::#ident(#attr)] });
+    quote!(#item).into()
+}
+
+fn is_companion_attr(attr: &syn::Attribute, name: &str) -> bool {
+    let segs = &attr.path.segments;
+    segs.len() == 2 && segs[0].ident == "argio" && segs[1].ident == name
+}
+
+struct Generator {
+    krate: syn::Path,
+}
+
+impl syn::parse::Parse for Generator {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut krate = parse_quote! { ::argio };
+        let mut seen = vec![];
+
+        while !input.is_empty() {
+            let var = input.call(syn::ext::IdentExt::parse_any)?;
+            check_duplicate(&var, &mut seen)?;
+
+            if var == "crate" {
+                input.parse::<Token![=]>()?;
+                krate = input.parse()?;
+            } else {
+                return Err(invalid_attr(&var, "generator attr", GENERATOR_ATTRS));
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(Generator { krate })
+    }
+}
+
+struct Stress {
+    gen: syn::Expr,
+    brute: Option<syn::Path>,
+    iters: Option<syn::Expr>,
+}
+
+impl syn::parse::Parse for Stress {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let mut gen = None;
+        let mut brute = None;
+        let mut iters = None;
+        let mut seen = vec![];
+
+        while !input.is_empty() {
+            let var = input.call(syn::ext::IdentExt::parse_any)?;
+            check_duplicate(&var, &mut seen)?;
+
+            if var == "gen" {
+                input.parse::<Token![=]>()?;
+                gen = Some(input.parse()?);
+            } else if var == "brute" {
+                input.parse::<Token![=]>()?;
+                brute = Some(input.parse()?);
+            } else if var == "iters" {
+                input.parse::<Token![=]>()?;
+                iters = Some(input.parse()?);
+            } else {
+                return Err(invalid_attr(&var, "stress attr", STRESS_ATTRS));
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        match gen {
+            Some(gen) => Ok(Stress { gen, brute, iters }),
+            None => Err(syn::Error::new(span, "argio: argio::stress requires gen")),
+        }
+    }
+}
+
+struct Property {
+    check: syn::Expr,
+    iters: Option<syn::Expr>,
+}
+
+impl syn::parse::Parse for Property {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let mut check = None;
+        let mut iters = None;
+        let mut seen = vec![];
+
+        while !input.is_empty() {
+            let var = input.call(syn::ext::IdentExt::parse_any)?;
+            check_duplicate(&var, &mut seen)?;
+
+            if var == "check" {
+                input.parse::<Token![=]>()?;
+                check = Some(input.parse()?);
+            } else if var == "iters" {
+                input.parse::<Token![=]>()?;
+                iters = Some(input.parse()?);
+            } else {
+                return Err(invalid_attr(&var, "property attr", PROPERTY_ATTRS));
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        match check {
+            Some(check) => Ok(Property { check, iters }),
+            None => Err(syn::Error::new(
+                span,
+                "argio: argio::property requires check",
+            )),
+        }
+    }
+}
+
+// Makes a closure generating a random input for `args`. Each parameter is written on its own
+// line, and the generated values are bound to the parameters, so that they can be used as the
+// lengths of the following ones.
+fn input_generator(args: &[Param]) -> syn::Result<proc_macro2::TokenStream> {
+    let mut stmts = vec![];
+    for arg in args.iter().filter(|arg| !arg.is_external()) {
+        if arg.attr.until_blank {
+            return Err(syn::Error::new_spanned(
+                &arg.arg,
+                "argio: until_blank cannot be used with argio::property",
+            ));
+        }
+        let value = random_value(&arg.arg.ty, arg.attr.range.as_ref(), arg.attr.len.as_ref())?;
+        let pat = match arg.arg.pat.as_ref() {
+            syn::Pat::Ident(pat) => {
+                let ident = &pat.ident;
+                quote! { #ident }
+            }
+            _ => quote! { _ },
+        };
+        stmts.push(quote! {
+            #[allow(unused_variables)]
+            let #pat = #value;
+            __argio_out.push('\n');
+        });
+    }
+    Ok(quote! {
+        |__argio_rng: &mut __argio::random::Rng| -> String {
+            let mut __argio_out = String::new();
+            #(#stmts)*
+            __argio_out
+        }
+    })
+}
+
+// Makes an expression generating a random value of `ty` and appending it to the input.
+// `range` applies to the scalars, and `len` to the length of the outermost sequence.
+fn random_value(
+    ty: &syn::Type,
+    range: Option<&syn::Expr>,
+    len: Option<&syn::Expr>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let push = |value: proc_macro2::TokenStream| {
+        quote! { __argio::testing::push_token(&mut __argio_out, &#value); }
+    };
+    let unsupported = || {
+        syn::Error::new_spanned(
+            ty,
+            "argio: cannot generate random values of this type for argio::property",
+        )
+    };
+    let random_len = |default: proc_macro2::TokenStream| match len {
+        Some(len) => quote! { __argio_rng.range(#len) },
+        None => default,
+    };
+
+    match ty {
+        syn::Type::Path(path) => {
+            let name = match path.path.segments.last() {
+                Some(seg) if seg.arguments.is_empty() => seg.ident.to_string(),
+                _ => return Err(unsupported()),
+            };
+            let range = |default: proc_macro2::TokenStream| match range {
+                Some(range) => quote! { #range },
+                None => default,
+            };
+            let scalar = |range: proc_macro2::TokenStream| {
+                let push = push(quote! { value });
+                quote! {{
+                    let value: #ty = __argio_rng.range(#range);
+                    #push
+                    value
+                }}
+            };
+            Ok(match name.as_str() {
+                "u8" | "u16" | "u32" | "u64" | "usize" => scalar(range(quote! { 0..=10 })),
+                "i8" | "i16" | "i32" | "i64" | "isize" => scalar(range(quote! { -10..=10 })),
+                "f32" | "f64" => scalar(range(quote! { -10.0..=10.0 })),
+                "char" => scalar(range(quote! { 'a'..='z' })),
+                "Usize1" | "Isize1" if is_marker(&path.path) => {
+                    let range = range(quote! { 0..10 });
+                    let push = push(quote! { (value + 1) });
+                    let value_ty = if name == "Usize1" {
+                        quote! { usize }
+                    } else {
+                        quote! { isize }
+                    };
+                    quote! {{
+                        let value: #value_ty = __argio_rng.range(#range);
+                        #push
+                        value
+                    }}
+                }
+                "String" | "Chars" | "Bytes" if name == "String" || is_marker(&path.path) => {
+                    let len = random_len(quote! { __argio_rng.range(1..=10) });
+                    let chars = range(quote! { 'a'..='z' });
+                    let push = push(quote! { value });
+                    let convert = match name.as_str() {
+                        "String" => quote! { value },
+                        "Chars" => quote! { value.chars().collect::<Vec<char>>() },
+                        _ => quote! { value.into_bytes() },
+                    };
+                    quote! {{
+                        let len: usize = #len;
+                        let value = (0..len)
+                            .map(|_| __argio_rng.range::<char>(#chars))
+                            .collect::<String>();
+                        #push
+                        #convert
+                    }}
+                }
+                _ => return Err(unsupported()),
+            })
+        }
+        syn::Type::Array(array) => {
+            let elem = random_value(&array.elem, range, None)?;
+            let len = &array.len;
+            Ok(quote! {
+                (0..#len).map(|_| #elem).collect::<Vec<_>>()
+            })
+        }
+        syn::Type::Slice(slice) => {
+            let elem = random_value(&slice.elem, range, None)?;
+            let len = random_len(quote! { __argio_rng.range(0..=10) });
+            let push = push(quote! { len });
+            Ok(quote! {{
+                let len: usize = #len;
+                #push
+                (0..len).map(|_| #elem).collect::<Vec<_>>()
+            }})
+        }
+        syn::Type::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(|elem| random_value(elem, None, None))
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote! { (#(#elems,)*) })
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+struct SampleTest {
+    name: Option<syn::Ident>,
+    input: syn::Expr,
+    expected: Option<Expected>,
+}
+
+enum Expected {
+    Value(Box<syn::Expr>),
+    // A snapshot file, which is written by the test if `ARGIO_UPDATE=1`.
+    Snapshot(String),
+}
+
+// Finds the sample inputs matching `pattern` and their outputs with the extension `.out`.
+fn sample_files(pattern: &syn::LitStr, fn_name: &syn::Ident) -> syn::Result<Vec<SampleTest>> {
+    let error = |msg: String| syn::Error::new(pattern.span(), format!("argio: {}", msg));
+
+    let value = pattern.value();
+    let (dir, file_pattern) = match value.rfind('/') {
+        Some(pos) => (&value[..pos], &value[pos + 1..]),
+        None => (".", &value[..]),
+    };
+    let dir = manifest_path(dir);
+
+    let entries = std::fs::read_dir(&dir)
+        .map_err(|err| error(format!("failed to read `{}`: {}", dir.display(), err)))?;
+    let mut inputs = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| glob_match(file_pattern, name))
+        })
+        .collect::<Vec<_>>();
+    inputs.sort();
+    if inputs.is_empty() {
+        return Err(error(format!("no files match `{}`", value)));
+    }
+
+    let mut ret = vec![];
+    for input in inputs {
+        let output = input.with_extension("out");
+        if output == input {
+            return Err(error(format!(
+                "the input `{}` has the extension of the output",
+                input.display()
+            )));
+        }
+        let stem = test_name_part(&input.file_stem().unwrap().to_string_lossy());
+        let input = input.to_string_lossy().into_owned();
+        let output = output.to_string_lossy().into_owned();
+        ret.push(SampleTest {
+            name: Some(quote::format_ident!("{}_{}", fn_name, stem)),
+            input: parse_quote! { include_str!(#input) },
+            expected: Some(Expected::Snapshot(output)),
+        });
+    }
+    Ok(ret)
+}
+
+// Turns `s` into a part of the name of a test.
+fn test_name_part(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// The test suite of a problem written by cargo-compete.
+struct CompeteSuite {
+    path: String,
+    tests: Vec<SampleTest>,
+    time_limit: Option<u64>,
+    tolerance: Option<f64>,
+}
+
+// Reads the test suite `path`, or `testcases/{alias}.yml` for the binary being compiled, which is
+// where cargo-compete saves it by default.
+//
+// Only the YAML written by cargo-compete is supported: `type`, `timelimit`, `match` and `cases`
+// at the top level, and `name`, `in` and `out` of each case.
+fn compete_suite(path: Option<&syn::LitStr>, fn_name: &syn::Ident) -> syn::Result<CompeteSuite> {
+    let span = path.map_or_else(proc_macro2::Span::call_site, |path| path.span());
+    let error = |msg: String| syn::Error::new(span, format!("argio: {}", msg));
+
+    let path = match path {
+        Some(path) => manifest_path(&path.value()),
+        None => {
+            let bin = std::env::var("CARGO_BIN_NAME")
+                .or_else(|_| std::env::var("CARGO_CRATE_NAME").map(|name| name.replace('_', "-")))
+                .map_err(|_| {
+                    error(
+                        "the name of the binary is unknown; give the path with compete = \"...\""
+                            .into(),
+                    )
+                })?;
+            let alias = compete_alias(&bin).unwrap_or(bin);
+            manifest_path(&format!("testcases/{}.yml", alias))
+        }
+    };
+    let yaml = std::fs::read_to_string(&path)
+        .map_err(|err| error(format!("failed to read `{}`: {}", path.display(), err)))?;
+
+    let mut ret = CompeteSuite {
+        path: path.to_string_lossy().into_owned(),
+        tests: vec![],
+        time_limit: None,
+        tolerance: None,
+    };
+    let lines = yaml.lines().collect::<Vec<_>>();
+    let mut cases: Vec<Vec<(String, String)>> = vec![];
+    let mut section = "";
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+        let content = line.trim();
+        if content.is_empty() || content.starts_with('#') || content == "---" {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            let (key, value) = content.split_once(':').unwrap_or((content, ""));
+            let value = value.trim();
+            section = "";
+            match key {
+                "type" if value != "Batch" => {
+                    return Err(error(format!(
+                        "only Batch problems are supported, not `{}`",
+                        value
+                    )));
+                }
+                "timelimit" => {
+                    let limit = yaml_scalar(value);
+                    ret.time_limit = Some(
+                        parse_duration(&limit)
+                            .ok_or_else(|| error(format!("invalid timelimit `{}`", limit)))?,
+                    );
+                }
+                "match" => {
+                    // `Float` has `relative_error` and `absolute_error` in the following lines.
+                    let mut text = value.to_string();
+                    while i < lines.len() && lines[i].starts_with(' ') {
+                        text += lines[i];
+                        i += 1;
+                    }
+                    if text.contains("Checker") {
+                        return Err(error(
+                            "match = Checker is not supported; use checker in #[argio]".into(),
+                        ));
+                    }
+                    if text.contains("Float") {
+                        let errors = ["relative_error", "absolute_error"]
+                            .iter()
+                            .filter_map(|name| yaml_number(&text, name));
+                        ret.tolerance = errors.reduce(f64::max);
+                    }
+                }
+                "cases" => section = "cases",
+                _ => {}
+            }
+            continue;
+        }
+        if section != "cases" {
+            continue;
+        }
+        let content = match content.strip_prefix("- ") {
+            Some(content) => {
+                cases.push(vec![]);
+                content
+            }
+            None => content,
+        };
+        let case = cases
+            .last_mut()
+            .ok_or_else(|| error(format!("unexpected line `{}`", content)))?;
+        let (key, value) = content
+            .split_once(':')
+            .ok_or_else(|| error(format!("unexpected line `{}`", content)))?;
+        let value = value.trim();
+        let value = if let Some(chomp) = value.strip_prefix('|') {
+            let mut block = vec![];
+            while i < lines.len()
+                && (lines[i].trim().is_empty()
+                    || lines[i].len() - lines[i].trim_start().len() > indent)
+            {
+                block.push(lines[i]);
+                i += 1;
+            }
+            yaml_block(&block, chomp)
+        } else {
+            yaml_scalar(value)
+        };
+        case.push((key.trim().to_string(), value));
+    }
+
+    for (n, case) in cases.iter().enumerate() {
+        let field = |name: &str| {
+            case.iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value)
+        };
+        let name =
+            field("name").map_or_else(|| format!("case{}", n + 1), |name| test_name_part(name));
+        let input = field("in").ok_or_else(|| error(format!("`{}` has no `in`", name)))?;
+        let output = field("out").ok_or_else(|| error(format!("`{}` has no `out`", name)))?;
+        let input = syn::LitStr::new(input, span);
+        let output = syn::LitStr::new(output, span);
+        ret.tests.push(SampleTest {
+            name: Some(quote::format_ident!("{}_{}", fn_name, name)),
+            input: parse_quote! { #input },
+            expected: Some(Expected::Value(Box::new(parse_quote! { #output }))),
+        });
+    }
+    Ok(ret)
+}
+
+// Finds the alias of the binary `bin` in `[package.metadata.cargo-compete.bin]` of `Cargo.toml`,
+// which is either `bin = { alias = "a", ... }` or `a = { name = "bin", ... }`.
+fn compete_alias(bin: &str) -> Option<String> {
+    let manifest = std::fs::read_to_string(manifest_path("Cargo.toml")).ok()?;
+    let field = |value: &str, name: &str| -> Option<String> {
+        let rest = &value[value.find(&format!("{} = \"", name))? + name.len() + 4..];
+        Some(rest[..rest.find('"')?].to_string())
+    };
+    let mut in_table = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_table = line == "[package.metadata.cargo-compete.bin]";
+            continue;
+        }
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) if in_table => (key.trim().trim_matches('"'), value),
+            _ => continue,
+        };
+        if key == bin {
+            return field(value, "alias");
+        }
+        if field(value, "name").as_deref() == Some(bin) {
+            return Some(key.to_string());
+        }
+    }
+    None
+}
+
+// Reads a block scalar of YAML, whose lines are `block`, with the chomping indicator `chomp`.
+fn yaml_block(block: &[&str], chomp: &str) -> String {
+    let indent = block
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut ret = String::new();
+    for line in block {
+        ret += line.get(indent..).unwrap_or("");
+        ret.push('\n');
+    }
+    match chomp.trim() {
+        "+" => ret,
+        "-" => ret.trim_end_matches('\n').to_string(),
+        _ => {
+            let body = ret.trim_end_matches('\n');
+            if body.is_empty() {
+                String::new()
+            } else {
+                format!("{}\n", body)
+            }
+        }
+    }
+}
+
+// Reads a plain or quoted scalar of YAML.
+fn yaml_scalar(value: &str) -> String {
+    if let Some(value) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        return value.replace("''", "'");
+    }
+    let value = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(value) => value,
+        None => return value.to_string(),
+    };
+    let mut ret = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            ret.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => ret.push('\n'),
+            Some('t') => ret.push('\t'),
+            Some('r') => ret.push('\r'),
+            Some(c) => ret.push(c),
+            None => {}
+        }
+    }
+    ret
+}
+
+// Finds the number of the field `name` in `text`, such as `relative_error: 1e-6`.
+fn yaml_number(text: &str, name: &str) -> Option<f64> {
+    let rest = text[text.find(name)? + name.len()..].trim_start();
+    let rest = rest.strip_prefix(':')?.trim_start();
+    let end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '+'))
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+// Collects the code blocks labeled `input` and `output` in the doc comment, which are paired in
+// the order of appearance.
+fn doc_samples(attrs: &[syn::Attribute], fn_name: &syn::Ident) -> syn::Result<Vec<SampleTest>> {
+    let mut inputs = vec![];
+    let mut outputs = vec![];
+    let mut block: Option<(String, Vec<String>, &syn::Attribute)> = None;
+
+    for attr in attrs {
+        if !attr.path.is_ident("doc") {
+            continue;
+        }
+        let line = match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(lit),
+                ..
+            })) => lit.value(),
+            _ => continue,
+        };
+        // Each line of a doc comment is a separate attribute unless it is a block comment.
+        for line in line.lines() {
+            let line = line.strip_prefix(' ').unwrap_or(line);
+            let fence = line.trim_start().strip_prefix("```");
+            match (&mut block, fence) {
+                (None, Some(info)) => block = Some((info.trim().to_string(), vec![], attr)),
+                (Some(_), Some(_)) => {
+                    let (info, lines, attr) = block.take().unwrap();
+                    let mut text = lines.join("\n");
+                    text.push('\n');
+                    match info.as_str() {
+                        "input" => inputs.push((text, attr)),
+                        "output" => outputs.push((text, attr)),
+                        _ => {}
+                    }
+                }
+                (Some((_, lines, _)), None) => lines.push(line.to_string()),
+                (None, None) => {}
+            }
+        }
+    }
+
+    if inputs.len() != outputs.len() {
+        let (_, attr) = inputs
+            .get(outputs.len())
+            .or_else(|| outputs.get(inputs.len()))
+            .unwrap();
+        return Err(syn::Error::new_spanned(
+            attr,
+            format!(
+                "argio: {} input and {} output blocks in the doc comment",
+                inputs.len(),
+                outputs.len()
+            ),
+        ));
+    }
+
+    Ok(inputs
+        .into_iter()
+        .zip(outputs)
+        .enumerate()
+        .map(|(i, ((input, _), (expected, _)))| SampleTest {
+            name: Some(quote::format_ident!("{}_doc_sample_{}", fn_name, i + 1)),
+            input: parse_quote! { #input },
+            expected: Some(Expected::Value(Box::new(parse_quote! { #expected }))),
+        })
+        .collect())
+}
+
+// Matches `name` against `pattern`, where `*` matches any string and `?` matches any character.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let mut dp = vec![false; name.len() + 1];
+    dp[0] = true;
+    for p in &pattern {
+        let mut next = vec![false; name.len() + 1];
+        for j in 0..=name.len() {
+            next[j] = match p {
+                '*' => dp[j] || (j > 0 && next[j - 1]),
+                '?' => j > 0 && dp[j - 1],
+                c => j > 0 && dp[j - 1] && name[j - 1] == *c,
+            };
+        }
+        dp = next;
+    }
+    dp[name.len()]
+}
+
+impl syn::parse::Parse for SampleTest {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let mut sample_input = None;
+        let mut expected = None;
+        let mut seen = vec![];
+
+        while !input.is_empty() {
+            let var = input.parse::<syn::Ident>()?;
+            check_duplicate(&var, &mut seen)?;
+
+            if var == "input" {
+                input.parse::<Token![=]>()?;
+                sample_input = Some(input.parse()?);
+            } else if var == "expected" {
+                input.parse::<Token![=]>()?;
+                expected = Some(Expected::Value(Box::new(input.parse()?)));
+            } else {
+                return Err(invalid_attr(&var, "test attr", TEST_ATTRS));
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        match sample_input {
+            Some(input) => Ok(SampleTest {
+                name: None,
+                input,
+                expected,
+            }),
+            None => Err(syn::Error::new(span, "argio: argio::test requires input")),
+        }
+    }
+}
+
+fn pretty(expanded: &proc_macro2::TokenStream) -> String {
+    match syn::parse2::<syn::File>(expanded.clone()) {
+        Ok(file) => prettyplease::unparse(&file),
+        Err(_) => expanded.to_string(),
+    }
+}
+
+// Resolves a relative path from the root of the package.
+fn manifest_path(path: &str) -> std::path::PathBuf {
+    let path = std::path::PathBuf::from(path);
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(root) if path.is_relative() => std::path::Path::new(&root).join(path),
+        _ => path,
+    }
+}
+
+// Writes `code` to the file at `path`, relative to the crate root.
+fn write_file(path: &syn::LitStr, code: &str) -> syn::Result<()> {
+    let file = manifest_path(&path.value());
+    let write = || -> std::io::Result<()> {
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&file, code)
+    };
+    write().map_err(|err| {
+        syn::Error::new(
+            path.span(),
+            format!("argio: failed to write `{}`: {}", file.display(), err),
+        )
+    })
+}
+
+// Writes the source file of `item` with `item` replaced by `expanded` and the runtime appended.
+fn bundle(
+    path: &syn::LitStr,
+    item: &syn::ItemFn,
+    expanded: &proc_macro2::TokenStream,
+) -> syn::Result<()> {
+    let call_site = proc_macro::Span::call_site();
+    let source = call_site
+        .local_file()
+        .and_then(|file| std::fs::read_to_string(file).ok())
+        .ok_or_else(|| syn::Error::new(path.span(), "argio: failed to read the source file"))?;
+
+    let first = item
+        .attrs
+        .iter()
+        .map(|attr| attr.pound_token.span.unwrap().start().line())
+        .chain([
+            call_site.start().line(),
+            item.sig.fn_token.span.unwrap().start().line(),
+        ])
+        .min()
+        .unwrap();
+    let last = item.block.brace_token.span.unwrap().end().line();
+
+    let mut code = String::new();
+    for (i, line) in source.lines().enumerate() {
+        if i + 1 == first {
+            code += &pretty(expanded);
+        } else if (first..=last).contains(&(i + 1)) || line.trim() == "use argio::argio;" {
+            continue;
+        } else {
+            code += line;
+            code += "\n";
+        }
+    }
+    code += "\n";
+    code += &runtime::runtime(&code);
+    write_file(path, &code)
+}
+
+const SUPPORTED_PARAMS: &str =
+    "supported forms are `x: T`, `mut x: T`, `(x, y): (T, U)`, `a: [T; n]` and `a: [T]`";
+
+// Checks that the signature only contains the forms that the input macro can handle.
+fn check_sig(sig: &syn::Signature) -> syn::Result<()> {
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "argio: generic parameters are not supported; the types of the parameters must be concrete",
+        ));
+    }
+    if let Some(where_clause) = &sig.generics.where_clause {
+        return Err(syn::Error::new_spanned(
+            where_clause,
+            "argio: where clauses are not supported",
+        ));
+    }
+    if let Some(variadic) = &sig.variadic {
+        return Err(syn::Error::new_spanned(
+            variadic,
+            "argio: variadic parameters are not supported",
+        ));
+    }
+    for input in &sig.inputs {
+        if let syn::FnArg::Typed(arg) = input {
+            check_pat(&arg.pat)?;
+            check_type(&arg.ty)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_pat(pat: &syn::Pat) -> syn::Result<()> {
+    match pat {
+        syn::Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => Ok(()),
+        syn::Pat::Tuple(tuple) => tuple.elems.iter().try_for_each(check_pat),
+        syn::Pat::Wild(_) => Ok(()),
+        _ => Err(syn::Error::new_spanned(
+            pat,
+            format!("argio: unsupported parameter pattern; {}", SUPPORTED_PARAMS),
+        )),
+    }
+}
+
+fn check_type(ty: &syn::Type) -> syn::Result<()> {
+    let what = match ty {
+        syn::Type::Array(syn::TypeArray { elem, .. })
+        | syn::Type::Slice(syn::TypeSlice { elem, .. })
+        | syn::Type::Group(syn::TypeGroup { elem, .. })
+        | syn::Type::Paren(syn::TypeParen { elem, .. }) => return check_type(elem),
+        syn::Type::Tuple(tuple) => return tuple.elems.iter().try_for_each(check_type),
+        syn::Type::Path(_) | syn::Type::Macro(_) | syn::Type::Verbatim(_) => return Ok(()),
+        syn::Type::Reference(_) => {
+            "references are not supported; use an owned type such as `String` or `[T; n]`"
+        }
+        syn::Type::Ptr(_) => "raw pointers are not supported",
+        syn::Type::ImplTrait(_) => "`impl Trait` is not supported; use a concrete type",
+        syn::Type::TraitObject(_) => "trait objects are not supported; use a concrete type",
+        syn::Type::BareFn(_) => "function pointers are not supported",
+        syn::Type::Infer(_) => "the type of a parameter cannot be inferred; write it explicitly",
+        syn::Type::Never(_) => "`!` is not supported",
+        _ => "unsupported parameter type",
+    };
+    Err(syn::Error::new_spanned(
+        ty,
+        format!("argio: {}; {}", what, SUPPORTED_PARAMS),
+    ))
+}
+
+fn entry_fn(
+    item: &syn::ItemFn,
+    args: &[Param],
+    fn_attrs: &[syn::Attribute],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let vis = &item.vis;
+    let asyncness = &item.sig.asyncness;
+    let name = &item.sig.ident;
+    let output = &item.sig.output;
+    let body = &item.block;
+
+    let mut inputs = vec![];
+    for arg in args {
+        let pat = &arg.arg.pat;
+        let ty = entry_type(&arg.arg.ty, arg.attr.from_str);
+        inputs.push(quote! { #pat: #ty });
+    }
+
+    Ok(quote! {
+        #(#fn_attrs)*
+        #vis #asyncness fn #name(#(#inputs),*) #output #body
+    })
+}
+
+fn entry_type(ty: &syn::Type, from_str: bool) -> syn::Type {
+    match ty {
+        syn::Type::Array(syn::TypeArray { elem, .. })
+        | syn::Type::Slice(syn::TypeSlice { elem, .. }) => {
+            let elem = entry_type(elem, from_str);
+            parse_quote! { Vec<#elem> }
+        }
+        syn::Type::Tuple(tuple) => {
+            let elems = tuple.elems.iter().map(|elem| entry_type(elem, from_str));
+            parse_quote! { (#(#elems,)*) }
+        }
+        syn::Type::Path(path) if !from_str && path.qself.is_none() && is_marker(&path.path) => {
+            let ident = &path.path.segments.last().unwrap().ident;
+            if let Some(
+                syn::Type::Array(syn::TypeArray { elem, .. })
+                | syn::Type::Slice(syn::TypeSlice { elem, .. }),
+            ) = unzip_inner(ty)
+            {
+                if let syn::Type::Tuple(tuple) = elem.as_ref() {
+                    let elems = tuple.elems.iter().map(|elem| entry_type(elem, from_str));
+                    return parse_quote! { (#(Vec<#elems>,)*) };
+                }
+            }
+            if ["Interval", "Interval1", "HalfOpen", "HalfOpen1"]
+                .contains(&ident.to_string().as_str())
+            {
+                let elem = interval_elem(path);
+                parse_quote! { std::ops::Range<#elem> }
+            } else if ident == "Usize1" {
+                parse_quote! { usize }
+            } else if ident == "Isize1" {
+                parse_quote! { isize }
+            } else if ident == "Line" {
+                parse_quote! { String }
+            } else if ident == "Chars" {
+                parse_quote! { Vec<char> }
+            } else if ident == "Bytes" {
+                parse_quote! { Vec<u8> }
+            } else {
+                ty.clone()
+            }
+        }
+        _ => ty.clone(),
+    }
+}
+
+fn is_fastout(attr: &syn::Attribute) -> bool {
+    matches!(attr.path.segments.last(), Some(seg) if seg.ident == "fastout")
+}
+
+// Replaces the body expanded by `#[proconio::fastout]` with the original one, which is kept in
+// `let __proconio_res = { .. };` of the expansion. Returns whether it is replaced.
+fn unwrap_fastout(block: &mut syn::Block) -> bool {
+    let body = block.stmts.iter().find_map(|stmt| match stmt {
+        syn::Stmt::Local(syn::Local {
+            pat: syn::Pat::Ident(pat),
+            init: Some((_, init)),
+            ..
+        }) if pat.ident == "__proconio_res" => match &**init {
+            syn::Expr::Block(body) => Some(body.block.clone()),
+            _ => None,
+        },
+        _ => None,
+    });
+    match body {
+        Some(body) => {
+            *block = body;
+            true
+        }
+        None => false,
+    }
+}
+
+// Makes `?` usable in `block` by running it in a closure returning `Result`, and panicking on errors.
+fn try_block(block: &syn::Block, output: &syn::ReturnType) -> syn::Block {
+    let ret_type: syn::Type = match output {
+        syn::ReturnType::Default => parse_quote! { () },
+        syn::ReturnType::Type(_, ty) => parse_quote! { #ty },
+    };
+    let mut block = block.clone();
+    ReturnRewriter.visit_block_mut(&mut block);
+    let stmts = &block.stmts;
+    parse_quote! {{
+        match (|| -> std::result::Result<#ret_type, std::boxed::Box<dyn std::error::Error>> {
+            std::result::Result::Ok({ #(#stmts)* })
+        })() {
+            std::result::Result::Ok(ret) => ret,
+            std::result::Result::Err(err) => panic!("argio: the function failed: {}", err),
+        }
+    }}
+}
+
+fn is_number(ty: &syn::Type) -> bool {
+    const NUMBERS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64",
+    ];
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .is_some_and(|ident| NUMBERS.iter().any(|n| ident == n)),
+        _ => false,
+    }
+}
+
+fn is_unit_result(ty: &syn::Type) -> bool {
+    let path = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return false,
+    };
+    let seg = path.segments.last().unwrap();
+    if seg.ident != "Result" {
+        return false;
+    }
+    match &seg.arguments {
+        syn::PathArguments::AngleBracketed(args) => matches!(
+            args.args.first(),
+            Some(syn::GenericArgument::Type(syn::Type::Tuple(tuple))) if tuple.elems.is_empty()
+        ),
+        _ => false,
+    }
+}
+
+fn pat_expr(pat: &syn::Pat) -> syn::Result<syn::Expr> {
+    match pat {
+        syn::Pat::Ident(pat) => {
+            let ident = &pat.ident;
+            Ok(parse_quote! { #ident })
+        }
+        syn::Pat::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(pat_expr)
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(parse_quote! { (#(#elems,)*) })
+        }
+        _ => Err(syn::Error::new_spanned(
+            pat,
+            "argio: entry requires identifiers or tuples of them as parameters",
+        )),
+    }
+}
+
+struct Input {
+    path: syn::Path,
+    source: Option<syn::Ident>,
+}
+
+impl Input {
+    // The input macro, and the source of the input given to it, which is made by `argio` unless a
+    // custom input macro reads the standard input by itself.
+    fn new(attr: &ArgioAttr, args: &[Param]) -> Self {
+        Input {
+            path: if let Some(path) = &attr.input {
+                path.clone()
+            } else {
+                parse_quote! { __argio::__input }
+            },
+            source: if attr.json {
+                None
+            } else if attr.input.is_none()
+                || attr.fast_input
+                || attr.byte_input
+                || attr.input_str.is_some()
+                || attr.normalize_input
+                || attr.strict_input
+                || attr.files
+                || attr.interactive
+                || attr.record.is_some()
+                || attr.io_stats
+                || attr.multicase.as_ref().is_some_and(|m| m.eof)
+                || attr.sets.as_ref().is_some_and(|m| m.eof)
+                || args.iter().any(|arg| arg.attr.until_blank || arg.lines)
+            {
+                Some(parse_quote! { __argio_source })
+            } else {
+                None
+            },
+        }
+    }
+
+    fn read(&self, args: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let path = &self.path;
+        if let Some(source) = &self.source {
+            quote! {
+                #path ! {
+                    from &mut #source,
+                    #args
+                }
+            }
+        } else {
+            quote! {
+                #path ! {
+                    #args
+                }
+            }
+        }
+    }
+
+    fn read_arg(
+        &self,
+        case: Option<&syn::Ident>,
+        arg: &syn::PatType,
+        checks: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let pat = &arg.pat;
+        let label = match pat.as_ref() {
+            syn::Pat::Ident(pat) => pat.ident.to_string(),
+            _ => quote!(#pat).to_string(),
+        };
+        let read = self.read(quote! { #arg });
+        diagnose(
+            &label,
+            case,
+            quote! {
+                #read
+                #checks
+            },
+        )
+    }
+}
+
+fn diagnose(
+    label: &str,
+    case: Option<&syn::Ident>,
+    code: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let case = match case {
+        Some(case) => quote! { Some(#case) },
+        None => quote! { None },
+    };
+    quote! {
+        __argio::diag::enter(#label, #case);
+        #code
+        __argio::diag::leave();
+    }
+}
+
+fn read_args(
+    input: &Input,
+    case: Option<&syn::Ident>,
+    args: &[&Param],
+) -> proc_macro2::TokenStream {
+    let mut ret = quote! {};
+
+    // Each parameter is read in its own context, so that an error names the exact parameter.
+    for arg in args {
+        if !arg.attr.from_str
+            && !arg.attr.until_blank
+            && !arg.lines
+            && unzip_inner(&arg.arg.ty).is_none()
+        {
+            ret.extend(input.read_arg(case, &arg.arg, check_arg(arg)));
+            continue;
+        }
+
+        let pat = &arg.arg.pat;
+        let ty = &arg.arg.ty;
+        // Lines are read with the built-in backend, as the other backends only know tokens.
+        let read = |var: proc_macro2::TokenStream, ty: &syn::Type| match &input.source {
+            Some(source) if arg.lines => quote! {
+                __argio::scan! {
+                    from &mut #source,
+                    #var: #ty
+                }
+            },
+            _ => input.read(quote! { #var: #ty }),
+        };
+        if arg.lines && !arg.attr.until_blank {
+            let read = read(quote! { __argio_line }, ty);
+            let checks = check_arg(arg);
+            ret.extend(diagnose(
+                &quote!(#pat).to_string(),
+                case,
+                quote! {
+                    #read
+                    let #pat = __argio_line;
+                    #checks
+                },
+            ));
+            continue;
+        }
+        if let Some(records) = unzip_inner(ty) {
+            let read = input.read(quote! { __argio_records: #records });
+            let checks = check_arg(arg);
+            ret.extend(diagnose(
+                &quote!(#pat).to_string(),
+                case,
+                quote! {
+                    #read
+                    let #pat = __argio::Unzip::unzip(__argio_records);
+                    #checks
+                },
+            ));
+            continue;
+        }
+        let var = arg.ident();
+        let name = var.to_string();
+
+        if let (true, syn::Type::Slice(syn::TypeSlice { elem, .. })) =
+            (arg.attr.until_blank, ty.as_ref())
+        {
+            let source = input.source.as_ref().unwrap();
+            let read = read(quote! { __argio_elem }, elem);
+            let checks = check_arg(arg);
+            ret.extend(diagnose(
+                &name,
+                case,
+                quote! {
+                    let #pat = {
+                        let mut __argio_elems = vec![];
+                        while !__argio::scan::Source::blank_line(&mut #source) {
+                            #read
+                            __argio_elems.push(__argio_elem);
+                        }
+                        __argio_elems
+                    };
+                    #checks
+                },
+            ));
+            continue;
+        }
+
+        let (token_ty, parsed_ty, parse) = match ty.as_ref() {
+            syn::Type::Array(syn::TypeArray { elem, len, .. }) => (
+                quote! { [String; #len] },
+                quote! { Vec<#elem> },
+                quote! {
+                    #var.iter().map(|s| __argio::parse_token::<#elem>(#name, s)).collect()
+                },
+            ),
+            _ => (
+                quote! { String },
+                quote! { #ty },
+                quote! { __argio::parse_token::<#ty>(#name, &#var) },
+            ),
+        };
+
+        let read = input.read(quote! { #var: #token_ty });
+        let checks = check_arg(arg);
+        ret.extend(diagnose(
+            &name,
+            case,
+            quote! {
+                #read
+                let #pat: #parsed_ty = #parse;
+                #checks
+            },
+        ));
+    }
+
+    ret
+}
+
+// Returns whether `path` may refer to a marker, which is recognized by its name. It is written as
+// it is, such as `Line`, or under `marker` or `argio`, such as `argio::marker::Line`, so that the
+// types of the same names in other modules, such as `geometry::Line`, are left as they are.
+fn is_marker(path: &syn::Path) -> bool {
+    let segments = path.segments.iter().collect::<Vec<_>>();
+    match segments.as_slice() {
+        [_] => path.leading_colon.is_none(),
+        [.., parent, _] => parent.ident == "marker" || parent.ident == "argio",
+        [] => false,
+    }
+}
+
+// Returns `[T; n]` if `ty` is `Unzip<[T; n]>`, which reads the records into a tuple of vectors.
+fn unzip_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let seg = path.segments.last().unwrap();
+    if seg.ident != "Unzip" || !is_marker(path) {
+        return None;
+    }
+    match &seg.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(
+                    ty @ (syn::Type::Array(_) | syn::Type::Slice(_)),
+                )) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// The type of the bounds of an `Interval`, which is `usize` if omitted.
+fn interval_elem(path: &syn::TypePath) -> syn::Type {
+    match &path.path.segments.last().unwrap().arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(ty)) => ty.clone(),
+            _ => parse_quote! { usize },
+        },
+        _ => parse_quote! { usize },
+    }
+}
+
+// Reads the parameters with `arg` from the command line options and the ones with `env` from the
+// environment variables. The option comes first if both are given.
+fn option_args_code(args: &[&Param]) -> proc_macro2::TokenStream {
+    let mut ret = quote! {};
+    for arg in args {
+        let pat = &arg.arg.pat;
+        let ty = &arg.arg.ty;
+        let inner = option_inner(ty).unwrap_or(ty);
+        let (value, missing) = match (arg.attr.arg, &arg.attr.env) {
+            (false, Some(env)) => (
+                quote! { __argio::args::var::<#inner>(#env) },
+                quote! { __argio::args::missing_var(#env) },
+            ),
+            (_, env) => {
+                let name = option_name(arg.ident());
+                let value = quote! { __argio::args::value::<#inner>(#name) };
+                let value = match env {
+                    Some(env) => quote! { #value.or_else(|| __argio::args::var::<#inner>(#env)) },
+                    None => value,
+                };
+                (value, quote! { __argio::args::missing(#name) })
+            }
+        };
+        let value = if option_inner(ty).is_some() {
+            value
+        } else {
+            let default = match &arg.attr.default {
+                Some(default) => quote! { #default },
+                None => missing,
+            };
+            quote! { #value.unwrap_or_else(|| #default) }
+        };
+        let checks = check_arg(arg);
+        ret.extend(quote! {
+            let #pat: #ty = #value;
+            #checks
+        });
+    }
+    ret
+}
+
+// Returns the name of the command line option for the parameter `ident`.
+fn option_name(ident: &syn::Ident) -> String {
+    ident.to_string().replace('_', "-")
+}
+
+// Returns `T` if `ty` is `Option<T>`.
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let seg = path.segments.last().unwrap();
+    let std = path.segments.len() == 1
+        || path.segments.len() == 3
+            && ["std", "core"]
+                .iter()
+                .any(|krate| path.segments[0].ident == krate)
+            && path.segments[1].ident == "option";
+    if seg.ident != "Option" || !std {
+        return None;
+    }
+    match &seg.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// Reads the parameters from the fields of the same names in a JSON object for `format = json`.
+fn json_args(
+    args: &[&Param],
+    reader: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut ret = quote! {
+        let mut __argio_json = __argio::json::read(#reader);
+    };
+    for arg in args {
+        if arg.attr.from_str {
+            return Err(syn::Error::new_spanned(
+                &arg.arg,
+                "argio: from_str cannot be used with format = json",
+            ));
+        }
+        if !matches!(arg.arg.pat.as_ref(), syn::Pat::Ident(_)) {
+            return Err(syn::Error::new_spanned(
+                &arg.arg.pat,
+                "argio: format = json requires a simple identifier",
+            ));
+        }
+        let pat = &arg.arg.pat;
+        let var = arg.ident();
+        let name = var.to_string();
+        let ty = json_type(&arg.arg.ty)?;
+        let check_len = match arg.arg.ty.as_ref() {
+            syn::Type::Array(syn::TypeArray { len, .. }) => quote! {
+                __argio::json::check_len(#name, #var.len(), #len);
+            },
+            _ => quote! {},
+        };
+        let checks = check_arg(arg);
+        ret.extend(quote! {
+            let #pat: #ty = __argio::json::param(&mut __argio_json, #name);
+            #check_len
+            #checks
+        });
+    }
+    Ok(ret)
+}
+
+// Returns the type deserialized from JSON for the parameter type `ty`.
+fn json_type(ty: &syn::Type) -> syn::Result<syn::Type> {
+    match ty {
+        syn::Type::Array(syn::TypeArray { elem, .. })
+        | syn::Type::Slice(syn::TypeSlice { elem, .. }) => {
+            let elem = json_type(elem)?;
+            Ok(parse_quote! { Vec<#elem> })
+        }
+        syn::Type::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(json_type)
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(parse_quote! { (#(#elems,)*) })
+        }
+        syn::Type::Path(path)
+            if path.qself.is_none()
+                && is_marker(&path.path)
+                && ["Usize1", "Isize1", "Chars", "Bytes"]
+                    .iter()
+                    .any(|marker| path.path.segments.last().unwrap().ident == marker) =>
+        {
+            Err(syn::Error::new_spanned(
+                ty,
+                "argio: markers cannot be used with format = json",
+            ))
+        }
+        _ => Ok(ty.clone()),
+    }
+}
+
+fn check_arg(arg: &Param) -> proc_macro2::TokenStream {
+    if arg.attr.range.is_none() && arg.attr.len.is_none() {
+        return quote! {};
+    }
+
+    let var = arg.ident();
+    let name = var.to_string();
+    let mut ret = quote! {};
+
+    if let Some(len) = &arg.attr.len {
+        ret.extend(quote! {
+            __argio::check::len(#name, #var.len(), &(#len));
+        });
+    }
+
+    if let Some(range) = &arg.attr.range {
+        let mut depth = 0;
+        let mut ty = arg.arg.ty.as_ref();
+        while let syn::Type::Array(syn::TypeArray { elem, .. }) = ty {
+            depth += 1;
+            ty = elem.as_ref();
+        }
+
+        let indices = (0..depth)
+            .map(|i| quote::format_ident!("__argio_i{}", i))
+            .collect::<Vec<_>>();
+        let values = (0..depth)
+            .map(|i| quote::format_ident!("__argio_v{}", i))
+            .collect::<Vec<_>>();
+
+        let mut check = {
+            let value = values.last().unwrap_or(var);
+            quote! {
+                __argio::check::range(#name, &[#(#indices),*], #value, &(#range));
+            }
+        };
+        for i in (0..depth).rev() {
+            let index = &indices[i];
+            let value = &values[i];
+            let parent = if i == 0 { var } else { &values[i - 1] };
+            check = quote! {
+                for (#index, #value) in #parent.iter().enumerate() {
+                    #check
+                }
+            };
+        }
+        if depth == 0 {
+            check = quote! {
+                {
+                    let #var = &#var;
+                    #check
+                }
+            };
+        }
+        ret.extend(check);
+    }
+
+    quote! {
+        if cfg!(debug_assertions) {
+            #ret
+        }
+    }
+}
+
+struct Param {
+    arg: syn::PatType,
+    attr: ParamAttr,
+    // Whether the type has `Line`, which is read with the built-in backend.
+    lines: bool,
+}
+
+impl Param {
+    fn parse_all(
+        inputs: &syn::punctuated::Punctuated<syn::FnArg, Token![,]>,
+    ) -> syn::Result<Vec<Param>> {
+        let mut ret = vec![];
+
+        for input in inputs {
+            let mut arg = match input {
+                syn::FnArg::Typed(arg) => arg.clone(),
+                syn::FnArg::Receiver(recv) => {
+                    return Err(syn::Error::new_spanned(recv, "argio: invalid argument"))
+                }
+            };
+
+            let mut param = ParamAttr::default();
+            let mut attrs = vec![];
+            for attr in arg.attrs.drain(..) {
+                if attr.path.is_ident("argio") {
+                    param = attr.parse_args()?;
+                } else {
+                    attrs.push(attr);
+                }
+            }
+            arg.attrs = attrs;
+
+            let needs_ident = param.from_str
+                || param.arg
+                || param.range.is_some()
+                || param.len.is_some()
+                || param.until_blank;
+            if needs_ident && !matches!(arg.pat.as_ref(), syn::Pat::Ident(_)) {
+                return Err(syn::Error::new_spanned(
+                    &arg.pat,
+                    "argio: this parameter attr requires a simple identifier",
+                ));
+            }
+
+            if param.until_blank
+                && (param.from_str || !matches!(arg.ty.as_ref(), syn::Type::Slice(_)))
+            {
+                return Err(syn::Error::new_spanned(
+                    &arg.ty,
+                    "argio: until_blank requires a slice type `[T]`",
+                ));
+            }
+
+            let external = param.arg || param.env.is_some();
+            if external && (param.from_str || param.global || param.len.is_some()) {
+                return Err(syn::Error::new_spanned(
+                    &arg,
+                    "argio: arg and env cannot be used with from_str, global or len",
+                ));
+            }
+            if let Some(default) = &param.default {
+                if !external {
+                    return Err(syn::Error::new_spanned(
+                        default,
+                        "argio: default requires arg or env",
+                    ));
+                }
+                if option_inner(&arg.ty).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        default,
+                        "argio: default cannot be used with Option",
+                    ));
+                }
+            }
+
+            let mut rewriter = IntervalRewriter {
+                closed: !param.half_open,
+                one_based: param.one_based,
+                found: false,
+            };
+            if !param.from_str {
+                rewriter.visit_type_mut(&mut arg.ty);
+            }
+            if (param.half_open || param.one_based) && !rewriter.found {
+                return Err(syn::Error::new_spanned(
+                    &arg,
+                    "argio: half_open and one_based require an Interval parameter",
+                ));
+            }
+
+            if param.global && ret.iter().any(|p: &Param| !p.attr.global) {
+                return Err(syn::Error::new_spanned(
+                    &arg,
+                    "argio: global parameters must come before the others",
+                ));
+            }
+
+            let mut line_rewriter = LineRewriter { found: false };
+            if !param.from_str {
+                line_rewriter.visit_type_mut(&mut arg.ty);
+            }
+
+            ret.push(Param {
+                arg,
+                attr: param,
+                lines: line_rewriter.found,
+            });
+        }
+
+        Ok(ret)
+    }
+
+    fn vars(&self) -> Vec<syn::PatIdent> {
+        Param::pat_vars(&self.arg.pat)
+    }
+
+    fn pat_vars(pat: &syn::Pat) -> Vec<syn::PatIdent> {
+        let mut collector = VarCollector { vars: vec![] };
+        collector.visit_pat_mut(&mut pat.clone());
+        collector.vars
+    }
+
+    // Whether the parameter is taken from the command line or the environment instead of the input.
+    fn is_external(&self) -> bool {
+        self.attr.arg || self.attr.env.is_some()
+    }
+
+    fn ident(&self) -> &syn::Ident {
+        match self.arg.pat.as_ref() {
+            syn::Pat::Ident(pat) => &pat.ident,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ParamAttr {
+    from_str: bool,
+    global: bool,
+    arg: bool,
+    env: Option<syn::LitStr>,
+    default: Option<syn::Expr>,
+    range: Option<syn::Expr>,
+    len: Option<syn::Expr>,
+    half_open: bool,
+    one_based: bool,
+    until_blank: bool,
+}
+
+impl syn::parse::Parse for ParamAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut ret = ParamAttr::default();
+        let mut seen = vec![];
+
+        while !input.is_empty() {
+            let var = input.parse::<syn::Ident>()?;
+            check_duplicate(&var, &mut seen)?;
+
+            if var == "from_str" {
+                ret.from_str = true;
+            } else if var == "global" {
+                ret.global = true;
+            } else if var == "arg" {
+                ret.arg = true;
+            } else if var == "env" {
+                input.parse::<Token![=]>()?;
+                ret.env = Some(input.parse()?);
+            } else if var == "default" {
+                input.parse::<Token![=]>()?;
+                ret.default = Some(input.parse()?);
+            } else if var == "range" {
+                input.parse::<Token![=]>()?;
+                ret.range = Some(input.parse()?);
+            } else if var == "len" {
+                input.parse::<Token![=]>()?;
+                ret.len = Some(input.parse()?);
+            } else if var == "half_open" {
+                ret.half_open = true;
+            } else if var == "one_based" {
+                ret.one_based = true;
+            } else if var == "until_blank" {
+                ret.until_blank = true;
+            } else {
+                return Err(invalid_attr(&var, "parameter attr", PARAM_ATTRS));
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(ret)
+    }
+}
+
+fn header_args(
+    fmt: &str,
+    span: proc_macro2::Span,
+    rewriter: &mut VarRewriter,
+) -> syn::Result<(String, Vec<syn::Expr>)> {
+    let (fmt_str, fmt_args) = parse_format(fmt).map_err(|msg| syn::Error::new(span, msg))?;
+
+    let mut args = vec![];
+    for fmt_arg in &fmt_args {
+        let mut arg: syn::Expr = syn::parse_str(fmt_arg)
+            .map_err(|err| syn::Error::new(span, format!("{}: `{}`", err, fmt_arg)))?;
+        rewriter.visit_expr_mut(&mut arg);
+        args.push(arg);
+    }
+
+    Ok((fmt_str, args))
+}
+
+fn parse_first_line(s: &syn::LitStr) -> syn::Result<Vec<syn::PatType>> {
+    let parser = syn::punctuated::Punctuated::<syn::FnArg, Token![,]>::parse_terminated;
+    let args = syn::parse::Parser::parse_str(parser, &s.value())
+        .map_err(|err| syn::Error::new(s.span(), format!("argio: invalid first_line: {}", err)))?;
+
+    let mut ret = vec![];
+    for arg in args {
+        match arg {
+            syn::FnArg::Typed(arg) => ret.push(arg),
+            syn::FnArg::Receiver(_) => {
+                return Err(syn::Error::new(s.span(), "argio: invalid first_line"))
+            }
+        }
+    }
+
+    match ret.first() {
+        Some(first) if matches!(first.pat.as_ref(), syn::Pat::Ident(_)) => {
+            // The number of cases is taken as it is, so that it is never truncated.
+            let usize: syn::Type = parse_quote! { usize };
+            if *first.ty != usize {
+                return Err(syn::Error::new(
+                    s.span(),
+                    "argio: the number of cases in first_line must be usize",
+                ));
+            }
+            Ok(ret)
+        }
+        _ => Err(syn::Error::new(
+            s.span(),
+            "argio: first_line must start with the number of cases",
+        )),
+    }
+}
+
+fn parse_duration(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (num, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit() && c != '.')?);
+    let num = num.parse::<f64>().ok()?;
+    let scale = match unit.trim() {
+        "ms" => 1.0,
+        "s" => 1000.0,
+        "m" | "min" => 60_000.0,
+        _ => return None,
+    };
+    Some((num * scale).round() as u64)
+}
+
+fn parse_size(s: &str) -> Option<usize> {
+    let s = s.trim();
+    let pos = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (num, unit) = s.split_at(pos);
+    let num = num.parse::<usize>().ok()?;
+    let scale = match unit.trim() {
+        "" | "B" => 1,
+        "K" | "KB" | "KiB" => 1 << 10,
+        "M" | "MB" | "MiB" => 1 << 20,
+        "G" | "GB" | "GiB" => 1 << 30,
+        _ => return None,
+    };
+    num.checked_mul(scale)
+}
+
+fn parse_format(fmt: &str) -> Result<(String, Vec<String>), String> {
+    let mut fmt_str = String::new();
+    let mut args = vec![];
+    let mut chars = fmt.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                fmt_str.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                fmt_str.push_str("}}");
+            }
+            '{' => {
+                let mut arg = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => {
+                            return Err("Invalid multicase format: unmatched `{`".to_string())
+                        }
+                        Some(c) => arg.push(c),
+                    }
+                }
+
+                let (expr, spec) = split_format_spec(&arg);
+                if expr.trim().is_empty() {
+                    return Err("Invalid multicase format: empty placeholder `{}`".to_string());
+                }
+                fmt_str.push('{');
+                fmt_str.push_str(spec);
+                fmt_str.push('}');
+                args.push(expr.to_string());
+            }
+            '}' => return Err("Invalid multicase format: unmatched `}`".to_string()),
+            c => fmt_str.push(c),
+        }
+    }
+
+    Ok((fmt_str, args))
+}
+
+fn split_format_spec(arg: &str) -> (&str, &str) {
+    let bytes = arg.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b':' {
+            if bytes.get(i + 1) == Some(&b':') {
+                i += 2;
+                continue;
+            }
+            return (&arg[..i], &arg[i..]);
+        }
+        i += 1;
+    }
+    (arg, "")
+}
+
+// Wraps the values of `return` in `Ok`, except in closures and nested items.
+struct ReturnRewriter;
+
+impl syn::visit_mut::VisitMut for ReturnRewriter {
+    fn visit_expr_return_mut(&mut self, i: &mut syn::ExprReturn) {
+        syn::visit_mut::visit_expr_return_mut(self, i);
+        let expr = match i.expr.take() {
+            Some(expr) => expr,
+            None => parse_quote! { () },
+        };
+        i.expr = Some(parse_quote! { std::result::Result::Ok(#expr) });
+    }
+
+    fn visit_expr_closure_mut(&mut self, _: &mut syn::ExprClosure) {}
+
+    fn visit_expr_async_mut(&mut self, _: &mut syn::ExprAsync) {}
+
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+// Sets the conventions of `Interval`s in the type of a parameter from its attrs.
+struct IntervalRewriter {
+    closed: bool,
+    one_based: bool,
+    found: bool,
+}
+
+impl syn::visit_mut::VisitMut for IntervalRewriter {
+    fn visit_type_path_mut(&mut self, i: &mut syn::TypePath) {
+        syn::visit_mut::visit_type_path_mut(self, i);
+        if i.qself.is_some()
+            || i.path.segments.last().unwrap().ident != "Interval"
+            || !is_marker(&i.path)
+        {
+            return;
+        }
+        let elem = interval_elem(i);
+        // The input macros split the kinds at commas, so aliases with one parameter are used.
+        let alias = match (self.closed, self.one_based) {
+            (true, false) => quote! { Interval },
+            (true, true) => quote! { Interval1 },
+            (false, false) => quote! { HalfOpen },
+            (false, true) => quote! { HalfOpen1 },
+        };
+        *i = parse_quote! { __argio::marker::#alias<#elem> };
+        self.found = true;
+    }
+}
+
+// Makes `Line`s in the type of a parameter refer to the marker of `argio`.
+struct LineRewriter {
+    found: bool,
+}
+
+impl syn::visit_mut::VisitMut for LineRewriter {
+    fn visit_type_path_mut(&mut self, i: &mut syn::TypePath) {
+        if i.qself.is_none()
+            && i.path.segments.last().unwrap().ident == "Line"
+            && is_marker(&i.path)
+        {
+            *i = parse_quote! { __argio::marker::Line };
+            self.found = true;
+        }
+    }
+}
+
+struct ImmutableRewriter;
+
+impl syn::visit_mut::VisitMut for ImmutableRewriter {
+    fn visit_pat_ident_mut(&mut self, i: &mut syn::PatIdent) {
+        i.mutability = None;
+        syn::visit_mut::visit_pat_ident_mut(self, i);
+    }
+}
+
+struct VarCollector {
+    vars: Vec<syn::PatIdent>,
+}
+
+impl syn::visit_mut::VisitMut for VarCollector {
+    fn visit_pat_ident_mut(&mut self, i: &mut syn::PatIdent) {
+        self.vars.push(i.clone());
+        syn::visit_mut::visit_pat_ident_mut(self, i);
+    }
+}
+
+struct VarRewriter {
+    renames: Vec<(syn::Ident, syn::Ident)>,
+}
+
+impl VarRewriter {
+    fn new(
+        multicase: &Multicase,
+        case_id: &syn::Ident,
+        case_no: &syn::Ident,
+        cases: &syn::Ident,
+    ) -> Self {
+        let mut ret = VarRewriter { renames: vec![] };
+        ret.renames.push((parse_quote! { i }, case_id.clone()));
+        ret.renames.push((parse_quote! { case }, case_no.clone()));
+        ret.renames.push((parse_quote! { cases }, cases.clone()));
+        ret.add_names(multicase, case_id, cases);
+        ret
+    }
+
+    fn add_names(&mut self, multicase: &Multicase, case_id: &syn::Ident, cases: &syn::Ident) {
+        if let Some(case_index) = &multicase.case_index {
+            self.renames.push((case_index.clone(), case_id.clone()));
+        }
+        if let Some(cases_var) = &multicase.cases_var {
+            self.renames.push((cases_var.clone(), cases.clone()));
+        }
+    }
+}
+
+impl syn::visit_mut::VisitMut for VarRewriter {
+    fn visit_ident_mut(&mut self, i: &mut syn::Ident) {
+        if let Some((_, to)) = self.renames.iter().rev().find(|(from, _)| i == from) {
+            *i = to.clone();
+        }
+    }
+}
+
+struct Multicase {
+    format: (String, proc_macro2::Span),
+    case_format: Option<(String, proc_macro2::Span)>,
+    eof: bool,
+    count: Option<syn::Expr>,
+    cases_var: Option<syn::Ident>,
+    case_index: Option<syn::Ident>,
+    case_separator: Option<syn::LitStr>,
+    read_all: Option<Option<syn::Ident>>,
+    parallel: bool,
+    case_timeout: Option<u64>,
+    fallback: Option<syn::Expr>,
+    first_line: Option<Vec<syn::PatType>>,
+    manual_header: bool,
+}
+
+impl Multicase {
+    fn parse_attr(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            let s = input.parse::<syn::LitStr>()?;
+            let mut ret = Multicase::new(s.span());
+            ret.format = (s.value(), s.span());
+            Ok(ret)
+        } else if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            content.parse()
+        } else {
+            Ok(Multicase::new(input.span()))
+        }
+    }
+
+    fn new(span: proc_macro2::Span) -> Self {
+        Multicase {
+            format: ("Case #{case}: ".to_string(), span),
+            case_format: None,
+            eof: false,
+            count: None,
+            cases_var: None,
+            case_index: None,
+            case_separator: None,
+            read_all: None,
+            parallel: false,
+            case_timeout: None,
+            fallback: None,
+            first_line: None,
+            manual_header: false,
+        }
+    }
+}
+
+impl syn::parse::Parse for Multicase {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut ret = Multicase::new(input.span());
+        let span = input.span();
+        let mut has_format = false;
+        let mut seen = vec![];
+
+        while !input.is_empty() {
+            let var = input.parse::<syn::Ident>()?;
+            check_duplicate(&var, &mut seen)?;
+
+            if var == "format" {
+                input.parse::<Token![=]>()?;
+                let s = input.parse::<syn::LitStr>()?;
+                ret.format = (s.value(), s.span());
+                has_format = true;
+            } else if var == "case_format" {
+                input.parse::<Token![=]>()?;
+                let s = input.parse::<syn::LitStr>()?;
+                ret.case_format = Some((s.value(), s.span()));
+            } else if var == "eof" {
+                if ret.count.is_some() {
+                    return Err(syn::Error::new(
+                        var.span(),
+                        "argio: eof and count cannot be used together",
+                    ));
+                }
+                ret.eof = true;
+            } else if var == "count" {
+                if ret.eof {
+                    return Err(syn::Error::new(
+                        var.span(),
+                        "argio: eof and count cannot be used together",
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                ret.count = Some(input.parse()?);
+            } else if var == "cases_var" {
+                input.parse::<Token![=]>()?;
+                ret.cases_var = Some(input.parse()?);
+            } else if var == "case_index" {
+                input.parse::<Token![=]>()?;
+                ret.case_index = Some(input.parse()?);
+            } else if var == "case_separator" {
+                input.parse::<Token![=]>()?;
+                ret.case_separator = Some(input.parse()?);
+            } else if var == "header" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<syn::Ident>()?;
+                if value == "auto" {
+                    ret.manual_header = false;
+                } else if value == "manual" {
+                    ret.manual_header = true;
+                } else {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "argio: header must be auto or manual",
+                    ));
+                }
+            } else if var == "first_line" {
+                input.parse::<Token![=]>()?;
+                let s = input.parse::<syn::LitStr>()?;
+                ret.first_line = Some(parse_first_line(&s)?);
+            } else if var == "case_timeout" {
+                input.parse::<Token![=]>()?;
+                let s = input.parse::<syn::LitStr>()?;
+                let millis = parse_duration(&s.value()).ok_or_else(|| {
+                    syn::Error::new(s.span(), "argio: invalid duration (e.g. \"500ms\", \"5s\")")
+                })?;
+                ret.case_timeout = Some(millis);
+            } else if var == "fallback" {
+                input.parse::<Token![=]>()?;
+                ret.fallback = Some(input.parse()?);
+            } else if var == "parallel" {
+                ret.parallel = true;
+            } else if var == "read_all" {
+                if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    ret.read_all = Some(Some(input.parse()?));
+                } else {
+                    ret.read_all = Some(None);
+                }
+            } else {
+                return Err(invalid_attr(&var, "multicase attr", MULTICASE_ATTRS));
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        if has_format && ret.case_format.is_some() {
+            return Err(syn::Error::new(
+                span,
+                "argio: format and case_format cannot be used together",
+            ));
+        }
+
+        if ret.manual_header && ret.case_format.is_some() {
+            return Err(syn::Error::new(
+                span,
+                "argio: header = manual cannot be used with case_format",
+            ));
+        }
+
+        if ret.first_line.is_some() && (ret.eof || ret.count.is_some()) {
+            return Err(syn::Error::new(
+                span,
+                "argio: first_line cannot be used with eof or count",
+            ));
+        }
+
+        if ret.case_timeout.is_some() != ret.fallback.is_some() {
+            return Err(syn::Error::new(
+                span,
+                "argio: case_timeout and fallback must be used together",
+            ));
+        }
+
+        if ret.case_timeout.is_some()
+            && (ret.parallel || ret.read_all.as_ref().is_some_and(|n| n.is_some()))
+        {
+            return Err(syn::Error::new(
+                span,
+                "argio: case_timeout cannot be used with parallel or read_all = name",
+            ));
+        }
+
+        if ret.eof && ret.cases_var.is_some() && ret.read_all.is_none() && !ret.parallel {
+            return Err(syn::Error::new(
+                span,
+                "argio: cases_var cannot be used with eof",
+            ));
+        }
+
+        Ok(ret)
+    }
+}
+
+const ATTRS: &[&str] = &[
+    "multicase",
+    "sets",
+    "output",
+    "input",
+    "fast_input",
+    "byte_input",
+    "input_str",
+    "input_file_arg",
+    "normalize_input",
+    "strict_input",
+    "timing",
+    "bench",
+    "io_stats",
+    "tee",
+    "progress",
+    "files",
+    "entry",
+    "runner",
+    "wasm",
+    "executor",
+    "runtime",
+    "stack_size",
+    "memory_limit",
+    "dump",
+    "bundle",
+    "crate",
+    "fastout",
+    "try",
+    "tests",
+    "oj",
+    "compete",
+    "float_tolerance",
+    "checker",
+    "snapshot",
+    "interactive",
+    "judge",
+    "connect",
+    "deadline",
+    "on_deadline",
+    "record",
+    "format",
+    "flush",
+    "output_capacity",
+    "output_file",
+];
+
+const MULTICASE_ATTRS: &[&str] = &[
+    "format",
+    "case_format",
+    "eof",
+    "count",
+    "cases_var",
+    "case_index",
+    "case_separator",
+    "header",
+    "first_line",
+    "case_timeout",
+    "fallback",
+    "parallel",
+    "read_all",
+];
+
+const PARAM_ATTRS: &[&str] = &[
+    "from_str",
+    "global",
+    "arg",
+    "env",
+    "default",
+    "range",
+    "len",
+    "half_open",
+    "one_based",
+    "until_blank",
+];
+
+const TEST_ATTRS: &[&str] = &["input", "expected"];
+
+const GENERATOR_ATTRS: &[&str] = &["crate"];
+
+const STRESS_ATTRS: &[&str] = &["gen", "brute", "iters"];
+
+const PROPERTY_ATTRS: &[&str] = &["check", "iters"];
+
+const CONFLICTS: &[(&str, &str)] = &[
+    ("executor", "runtime"),
+    ("input_str", "files"),
+    ("input_str", "fast_input"),
+    ("input_str", "input_file_arg"),
+    ("files", "input_file_arg"),
+    ("fastout", "flush"),
+    ("checker", "float_tolerance"),
+    ("tests", "oj"),
+    ("interactive", "fast_input"),
+    ("interactive", "byte_input"),
+    ("fast_input", "byte_input"),
+    ("interactive", "input_str"),
+    ("interactive", "input_file_arg"),
+    ("interactive", "files"),
+    ("interactive", "flush"),
+    ("interactive", "record"),
+    ("interactive", "output_capacity"),
+    ("interactive", "output_file"),
+    ("files", "output_file"),
+    ("format", "multicase"),
+    ("format", "sets"),
+    ("format", "output"),
+    ("format", "input"),
+    ("format", "fast_input"),
+    ("format", "byte_input"),
+    ("format", "normalize_input"),
+    ("format", "strict_input"),
+    ("format", "files"),
+    ("format", "interactive"),
+    ("format", "bundle"),
+    ("wasm", "bundle"),
+];
+
+fn invalid_attr(var: &syn::Ident, kind: &str, valid: &[&str]) -> syn::Error {
+    let name = var.to_string();
+    let mut msg = format!("argio: invalid {}: {}", kind, name);
+    let nearest = valid
+        .iter()
+        .map(|v| (edit_distance(&name, v), *v))
+        .min()
+        .filter(|(d, _)| *d <= 2.max(name.len() / 3));
+    if let Some((_, v)) = nearest {
+        msg += &format!("; did you mean `{}`?", v);
+    }
+    syn::Error::new(var.span(), msg)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut dp = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = dp[0];
+        dp[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = dp[j + 1];
+            dp[j + 1] = if ca == *cb {
+                prev
+            } else {
+                prev.min(dp[j]).min(cur) + 1
+            };
+            prev = cur;
+        }
+    }
+    dp[b.len()]
+}
+
+fn check_duplicate(var: &syn::Ident, seen: &mut Vec<syn::Ident>) -> syn::Result<()> {
+    if seen.contains(var) {
+        return Err(syn::Error::new(
+            var.span(),
+            format!("argio: duplicate attr: {}", var),
+        ));
+    }
+    seen.push(var.clone());
+    Ok(())
+}
+
+fn check_conflicts(seen: &[syn::Ident], conflicts: &[(&str, &str)]) -> syn::Result<()> {
+    for (a, b) in conflicts {
+        let a = seen.iter().position(|var| var == a);
+        let b = seen.iter().position(|var| var == b);
+        if let (Some(a), Some(b)) = (a, b) {
+            let (first, second) = (&seen[a.min(b)], &seen[a.max(b)]);
+            return Err(syn::Error::new(
+                second.span(),
+                format!("argio: {} cannot be used with {}", second, first),
+            ));
+        }
+    }
+    Ok(())
+}
+
+struct ArgioAttr {
+    multicase: Option<Multicase>,
+    sets: Option<Multicase>,
+    input: Option<syn::Path>,
+    fast_input: bool,
+    byte_input: bool,
+    input_str: Option<syn::LitStr>,
+    input_file_arg: bool,
+    normalize_input: bool,
+    strict_input: bool,
+    timing: bool,
+    bench: bool,
+    io_stats: bool,
+    tee: Option<syn::LitStr>,
+    progress: bool,
+    files: bool,
+    entry: bool,
+    runner: Option<syn::Ident>,
+    wasm: Option<syn::Ident>,
+    executor: Option<(Executor, proc_macro2::Span)>,
+    stack_size: Option<usize>,
+    memory_limit: Option<usize>,
+    dump: Option<syn::LitStr>,
+    bundle: Option<syn::LitStr>,
+    krate: syn::Path,
+    try_body: bool,
+    tests: Option<syn::LitStr>,
+    oj: Option<syn::LitStr>,
+    compete: Option<Option<syn::LitStr>>,
+    float_tolerance: Option<syn::Expr>,
+    checker: Option<syn::Path>,
+    snapshot: Option<syn::LitStr>,
+    interactive: bool,
+    judge: Option<syn::Path>,
+    connect: Option<syn::LitStr>,
+    deadline: Option<(syn::LitStr, u64)>,
+    on_deadline: Option<syn::Path>,
+    record: Option<syn::LitStr>,
+    json: bool,
+    flush: Flush,
+    // Whether `flush` or `fastout` is given, which is not overridden by `#[proconio::fastout]`.
+    flush_given: bool,
+    output_capacity: Option<syn::Expr>,
+    output_file: Option<syn::LitStr>,
+    // The wrapper of the return value, and the arguments following the value.
+    output: Option<(syn::Path, Vec<syn::Expr>)>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Executor {
+    Pollster,
+    Futures,
+    Tokio,
+}
+
+impl Executor {
+    fn block_on(self) -> proc_macro2::TokenStream {
+        match self {
+            Executor::Pollster => quote! { __argio::pollster::block_on },
+            Executor::Futures => quote! { __argio::futures::executor::block_on },
+            Executor::Tokio => quote! { __argio::runtime::block_on },
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Flush {
+    PerCase,
+    AtEnd,
+    Once,
+    Never,
+}
+
+impl syn::parse::Parse for ArgioAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut ret = ArgioAttr {
+            multicase: None,
+            sets: None,
+            input: None,
+            fast_input: false,
+            byte_input: false,
+            input_str: None,
+            input_file_arg: false,
+            normalize_input: false,
+            strict_input: false,
+            timing: false,
+            bench: false,
+            io_stats: false,
+            tee: None,
+            progress: false,
+            files: false,
+            entry: false,
+            runner: None,
+            wasm: None,
+            executor: None,
+            stack_size: None,
+            memory_limit: None,
+            dump: None,
+            bundle: None,
+            krate: parse_quote! { ::argio },
+            try_body: false,
+            tests: None,
+            oj: None,
+            compete: None,
+            float_tolerance: None,
+            checker: None,
+            snapshot: None,
+            interactive: false,
+            judge: None,
+            connect: None,
+            deadline: None,
+            on_deadline: None,
+            record: None,
+            json: false,
+            output_capacity: None,
+            output_file: None,
+            flush: Flush::Never,
+            flush_given: false,
+            output: None,
+        };
+
+        let mut seen = vec![];
+
+        while !input.is_empty() {
+            let var = input.call(syn::ext::IdentExt::parse_any)?;
+            check_duplicate(&var, &mut seen)?;
+
+            if var == "multicase" {
+                ret.multicase = Some(Multicase::parse_attr(input)?);
+            } else if var == "sets" {
+                let sets = Multicase::parse_attr(input)?;
+                if sets.case_format.is_some()
+                    || sets.read_all.is_some()
+                    || sets.parallel
+                    || sets.case_timeout.is_some()
+                    || sets.first_line.is_some()
+                {
+                    return Err(syn::Error::new(
+                        var.span(),
+                        "argio: sets only supports format, eof, count, cases_var, case_index and case_separator",
+                    ));
+                }
+                ret.sets = Some(sets);
+            } else if var == "output" {
+                input.parse::<Token![=]>()?;
+                let path = input.parse::<syn::Path>()?;
+                let mut args = vec![];
+                if input.peek(syn::token::Paren) {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let exprs = content.parse_terminated::<_, Token![,]>(
+                        <syn::Expr as syn::parse::Parse>::parse,
+                    )?;
+                    args.extend(exprs);
+                }
+                ret.output = Some((path, args));
+            } else if var == "input" {
+                input.parse::<Token![=]>()?;
+                let path = input.parse::<syn::Path>()?;
+                ret.input = Some(path);
+            } else if var == "fast_input" {
+                ret.fast_input = true;
+            } else if var == "byte_input" {
+                ret.byte_input = true;
+            } else if var == "input_file_arg" {
+                ret.input_file_arg = true;
+            } else if var == "input_str" {
+                input.parse::<Token![=]>()?;
+                ret.input_str = Some(input.parse::<syn::LitStr>()?);
+            } else if var == "normalize_input" {
+                ret.normalize_input = true;
+            } else if var == "strict_input" {
+                ret.strict_input = true;
+            } else if var == "timing" {
+                ret.timing = true;
+            } else if var == "bench" {
+                ret.bench = true;
+            } else if var == "io_stats" {
+                ret.io_stats = true;
+            } else if var == "tee" {
+                if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    ret.tee = Some(input.parse()?);
+                } else {
+                    ret.tee = Some(syn::LitStr::new("", var.span()));
+                }
+            } else if var == "progress" {
+                ret.progress = true;
+            } else if var == "files" {
+                ret.files = true;
+            } else if var == "entry" {
+                ret.entry = true;
+            } else if var == "runner" {
+                if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    ret.runner = Some(input.parse()?);
+                } else {
+                    ret.runner = Some(parse_quote! { __argio_run });
+                }
+            } else if var == "wasm" {
+                input.parse::<Token![=]>()?;
+                ret.wasm = Some(input.parse()?);
+            } else if var == "executor" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<syn::Ident>()?;
+                let executor = if value == "pollster" {
+                    Executor::Pollster
+                } else if value == "futures" {
+                    Executor::Futures
+                } else {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "argio: executor must be pollster or futures",
+                    ));
+                };
+                ret.executor = Some((executor, var.span()));
+            } else if var == "runtime" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<syn::Ident>()?;
+                if value != "tokio" {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "argio: runtime must be tokio",
+                    ));
+                }
+                ret.executor = Some((Executor::Tokio, var.span()));
+            } else if var == "stack_size" {
+                input.parse::<Token![=]>()?;
+                let s = input.parse::<syn::LitStr>()?;
+                let size = parse_size(&s.value()).ok_or_else(|| {
+                    syn::Error::new(s.span(), "argio: invalid size (e.g. \"256MB\", \"1GB\")")
+                })?;
+                ret.stack_size = Some(size);
+            } else if var == "memory_limit" {
+                input.parse::<Token![=]>()?;
+                let s = input.parse::<syn::LitStr>()?;
+                let size = parse_size(&s.value()).ok_or_else(|| {
+                    syn::Error::new(s.span(), "argio: invalid size (e.g. \"256MB\", \"1GB\")")
+                })?;
+                ret.memory_limit = Some(size);
+            } else if var == "dump" {
+                input.parse::<Token![=]>()?;
+                ret.dump = Some(input.parse()?);
+            } else if var == "bundle" {
+                input.parse::<Token![=]>()?;
+                ret.bundle = Some(input.parse()?);
+            } else if var == "crate" {
+                input.parse::<Token![=]>()?;
+                ret.krate = input.parse()?;
+            } else if var == "tests" {
+                input.parse::<Token![=]>()?;
+                ret.tests = Some(input.parse()?);
+            } else if var == "compete" {
+                if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    ret.compete = Some(Some(input.parse()?));
+                } else {
+                    ret.compete = Some(None);
+                }
+            } else if var == "oj" {
+                if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    ret.oj = Some(input.parse()?);
+                } else {
+                    ret.oj = Some(syn::LitStr::new("test", var.span()));
+                }
+            } else if var == "float_tolerance" {
+                input.parse::<Token![=]>()?;
+                ret.float_tolerance = Some(input.parse()?);
+            } else if var == "checker" {
+                input.parse::<Token![=]>()?;
+                ret.checker = Some(input.parse()?);
+            } else if var == "snapshot" {
+                input.parse::<Token![=]>()?;
+                ret.snapshot = Some(input.parse()?);
+            } else if var == "interactive" {
+                ret.interactive = true;
+            } else if var == "judge" {
+                input.parse::<Token![=]>()?;
+                ret.judge = Some(input.parse()?);
+            } else if var == "connect" {
+                input.parse::<Token![=]>()?;
+                ret.connect = Some(input.parse()?);
+            } else if var == "deadline" {
+                input.parse::<Token![=]>()?;
+                let s = input.parse::<syn::LitStr>()?;
+                let millis = parse_duration(&s.value()).ok_or_else(|| {
+                    syn::Error::new(s.span(), "argio: invalid duration (e.g. \"500ms\", \"5s\")")
+                })?;
+                ret.deadline = Some((s, millis));
+            } else if var == "on_deadline" {
+                input.parse::<Token![=]>()?;
+                ret.on_deadline = Some(input.parse()?);
+            } else if var == "record" {
+                input.parse::<Token![=]>()?;
+                ret.record = Some(input.parse()?);
+            } else if var == "format" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<syn::Ident>()?;
+                if value != "json" {
+                    return Err(syn::Error::new(value.span(), "argio: format must be json"));
+                }
+                ret.json = true;
+            } else if var == "output_capacity" {
+                input.parse::<Token![=]>()?;
+                ret.output_capacity = Some(input.parse()?);
+            } else if var == "output_file" {
+                input.parse::<Token![=]>()?;
+                ret.output_file = Some(input.parse()?);
+            } else if var == "try" {
+                ret.try_body = true;
+            } else if var == "fastout" {
+                ret.flush = Flush::AtEnd;
+            } else if var == "flush" {
+                input.parse::<Token![=]>()?;
+                let value = input.parse::<syn::Ident>()?;
+                ret.flush = if value == "per_case" {
+                    Flush::PerCase
+                } else if value == "at_end" {
+                    Flush::AtEnd
+                } else if value == "once" {
+                    Flush::Once
+                } else if value == "never" {
+                    Flush::Never
+                } else {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "argio: flush must be per_case, at_end, once or never",
+                    ));
+                };
+            } else {
+                return Err(invalid_attr(&var, "attr", ATTRS));
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        check_conflicts(&seen, CONFLICTS)?;
+
+        ret.flush_given = seen.iter().any(|var| var == "flush" || var == "fastout");
+        if let Some(capacity) = &ret.output_capacity {
+            // A capacity is given to the buffer, so that it buffers the output unless the
+            // buffering is turned off explicitly.
+            if !ret.flush_given {
+                ret.flush = Flush::AtEnd;
+            }
+            if ret.flush == Flush::Never {
+                return Err(syn::Error::new_spanned(
+                    capacity,
+                    "argio: output_capacity cannot be used with flush = never",
+                ));
+            }
+        }
+
+        if let Some(judge) = &ret.judge {
+            if !ret.interactive {
+                return Err(syn::Error::new_spanned(
+                    judge,
+                    "argio: judge requires interactive",
+                ));
+            }
+        }
+
+        if let Some(connect) = &ret.connect {
+            if !ret.interactive {
+                return Err(syn::Error::new_spanned(
+                    connect,
+                    "argio: connect requires interactive",
+                ));
+            }
+        }
+
+        if let Some((deadline, _)) = &ret.deadline {
+            if !ret.interactive {
+                return Err(syn::Error::new_spanned(
+                    deadline,
+                    "argio: deadline requires interactive",
+                ));
+            }
+        }
+
+        if let Some(on_deadline) = &ret.on_deadline {
+            if ret.deadline.is_none() {
+                return Err(syn::Error::new_spanned(
+                    on_deadline,
+                    "argio: on_deadline requires deadline",
+                ));
+            }
+        }
+
+        if ret.bundle.is_some()
+            && (ret.executor.is_some() || ret.multicase.as_ref().is_some_and(|m| m.parallel))
+        {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "argio: bundle cannot be used with parallel, executor or runtime, which need other crates",
+            ));
+        }
+
+        let injects_names = |m: &Multicase| {
+            m.cases_var.is_some()
+                || m.case_index.is_some()
+                || m.read_all.as_ref().is_some_and(|n| n.is_some())
+                || m.manual_header
+        };
+        if ret.entry
+            && (ret.multicase.as_ref().is_some_and(injects_names)
+                || ret.sets.as_ref().is_some_and(injects_names))
+        {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "argio: entry cannot be used with cases_var, case_index, read_all = name or header = manual",
+            ));
+        }
+
+        if ret.sets.is_some() && ret.multicase.is_none() {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "argio: sets requires multicase",
+            ));
         }
 
         Ok(ret)
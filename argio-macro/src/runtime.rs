@@ -0,0 +1,297 @@
+// The runtime of `argio` embedded into the files written by `#[argio(bundle)]`.
+//
+// It is made from the sources of the modules of `argio` when the file is written, so that it
+// never goes out of sync with them. Only the modules referred to by the file are embedded, as
+// judges limit the size of a submission (e.g. 64 KB on Codeforces).
+
+use quote::ToTokens;
+use std::collections::BTreeSet;
+use syn::visit_mut::VisitMut;
+
+// The modules of `argio` which can be embedded, as they do not depend on other crates.
+const MODULES: &[(&str, &str)] = &[
+    ("args", include_str!("../../src/args.rs")),
+    ("check", include_str!("../../src/check.rs")),
+    ("diag", include_str!("../../src/diag.rs")),
+    ("io", include_str!("../../src/io.rs")),
+    ("marker", include_str!("../../src/marker.rs")),
+    ("memory", include_str!("../../src/memory.rs")),
+    ("output", include_str!("../../src/output.rs")),
+    ("progress", include_str!("../../src/progress.rs")),
+    ("scan", include_str!("../../src/scan.rs")),
+    ("timing", include_str!("../../src/timing.rs")),
+];
+
+const ROOT: &str = include_str!("../../src/lib.rs");
+
+// Makes `mod argio` with the modules referred to by `code`, the source of the submission.
+pub(crate) fn runtime(code: &str) -> String {
+    let mut modules = vec![];
+    let mut queue = referred(code, "argio::");
+    let root = parse(ROOT, &mut queue);
+    while let Some(name) = queue.pop() {
+        if modules.iter().any(|(done, _)| *done == name) {
+            continue;
+        }
+        if let Some((name, source)) = MODULES.iter().find(|(module, _)| *module == name) {
+            modules.push((*name, parse(source, &mut queue)));
+        }
+    }
+    modules.sort_by_key(|(name, _)| *name);
+
+    let mut items: Vec<syn::Item> = vec![
+        syn::parse_quote! { pub use crate::scan; },
+        syn::parse_quote! { pub use crate::scan as __input; },
+    ];
+    // The modules are inlined in place of their declarations.
+    items.extend(
+        root.items
+            .into_iter()
+            .filter(|item| !matches!(item, syn::Item::Mod(_) | syn::Item::Use(_))),
+    );
+    for (name, file) in modules {
+        let name = syn::Ident::new(name, proc_macro2::Span::call_site());
+        let body = file.items;
+        items.push(syn::parse_quote! {
+            pub mod #name {
+                #(#body)*
+            }
+        });
+    }
+    prune(&mut items, code);
+    let file: syn::File = syn::parse_quote! {
+        #[allow(dead_code, unused_imports)]
+        mod argio {
+            #(#items)*
+        }
+    };
+    prettyplease::unparse(&file)
+}
+
+// Parses the source of a module of `argio`, with the paths adjusted to live in `mod argio` of the
+// submission. The modules it refers to are pushed to `deps`.
+fn parse(source: &str, deps: &mut Vec<String>) -> syn::File {
+    let mut code = String::with_capacity(source.len());
+    for line in source.lines() {
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with("///") && !trimmed.starts_with("//!") {
+            code += line;
+            code += "\n";
+        }
+    }
+    deps.extend(referred(&code, "crate::"));
+    let mut file = syn::parse_file(&rebase(&code)).expect("argio: failed to parse the runtime");
+    strip_cfg(&mut file.items);
+    UseDeps(deps).visit_file_mut(&mut file);
+    file
+}
+
+// Collects the modules imported together, such as `args` in `use crate::argio::{args, diag};`.
+struct UseDeps<'a>(&'a mut Vec<String>);
+
+impl VisitMut for UseDeps<'_> {
+    fn visit_item_use_mut(&mut self, item: &mut syn::ItemUse) {
+        if let syn::UseTree::Path(krate) = &item.tree {
+            if let syn::UseTree::Path(argio) = &*krate.tree {
+                if krate.ident == "crate" && argio.ident == "argio" {
+                    self.push(&argio.tree);
+                }
+            }
+        }
+    }
+}
+
+impl UseDeps<'_> {
+    fn push(&mut self, tree: &syn::UseTree) {
+        match tree {
+            syn::UseTree::Path(path) => self.0.push(path.ident.to_string()),
+            syn::UseTree::Name(name) => self.0.push(name.ident.to_string()),
+            syn::UseTree::Rename(rename) => self.0.push(rename.ident.to_string()),
+            syn::UseTree::Group(group) => group.items.iter().for_each(|tree| self.push(tree)),
+            syn::UseTree::Glob(_) => {}
+        }
+    }
+}
+
+// Returns the names following `prefix` in `code`, such as the modules in `argio::io::print`.
+fn referred(code: &str, prefix: &str) -> Vec<String> {
+    let mut ret = BTreeSet::new();
+    for (i, _) in code.match_indices(prefix) {
+        let rest = &code[i + prefix.len()..];
+        let end = rest
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        ret.insert(rest[..end].to_string());
+    }
+    ret.into_iter().collect()
+}
+
+// Rewrites the paths `crate::x` and `$crate::x` into `crate::argio::x`, except the calls of the
+// macros exported at the root of the crate, such as `$crate::__scan_inner!`.
+fn rebase(code: &str) -> String {
+    let mut ret = String::with_capacity(code.len());
+    let mut rest = code;
+    while let Some(i) = rest.find("crate::") {
+        let (head, tail) = rest.split_at(i + "crate::".len());
+        ret += head;
+        let is_path = !head[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_');
+        let end = tail
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(tail.len());
+        if is_path && !tail[end..].starts_with('!') && !tail.starts_with("argio::") {
+            ret += "argio::";
+        }
+        rest = tail;
+    }
+    ret + rest
+}
+
+// Removes the items only for the features of `argio`, which are not available in the submission.
+fn strip_cfg(items: &mut Vec<syn::Item>) {
+    items.retain(|item| {
+        let attrs = match item {
+            syn::Item::Const(item) => &item.attrs,
+            syn::Item::Enum(item) => &item.attrs,
+            syn::Item::Fn(item) => &item.attrs,
+            syn::Item::Impl(item) => &item.attrs,
+            syn::Item::Macro(item) => &item.attrs,
+            syn::Item::Mod(item) => &item.attrs,
+            syn::Item::Static(item) => &item.attrs,
+            syn::Item::Struct(item) => &item.attrs,
+            syn::Item::Trait(item) => &item.attrs,
+            syn::Item::Type(item) => &item.attrs,
+            syn::Item::Use(item) => &item.attrs,
+            _ => return true,
+        };
+        !attrs.iter().any(|attr| {
+            attr.path.is_ident("cfg")
+                && matches!(
+                    attr.parse_meta(),
+                    Ok(syn::Meta::List(list)) if matches!(
+                        list.nested.first(),
+                        Some(syn::NestedMeta::Meta(syn::Meta::NameValue(meta)))
+                            if meta.path.is_ident("feature")
+                    )
+                )
+        })
+    });
+}
+
+// Removes the items which are never referred to by `code` or the items kept, as most of the
+// runtime is only for the options not used by the submission. The references are found by the
+// names, so that an item may be kept only because another one has the same name.
+fn prune(items: &mut Vec<syn::Item>, code: &str) {
+    let mut declared = BTreeSet::new();
+    for_each_item(items, &mut |item| declared.extend(item_name(item)));
+
+    let mut used = BTreeSet::new();
+    idents(code.parse().unwrap_or_default(), &mut used);
+    loop {
+        let len = used.len();
+        let mut found = BTreeSet::new();
+        for_each_item(items, &mut |item| {
+            if !matches!(item, syn::Item::Use(_)) && is_used(item, &declared, &used) {
+                idents(item.to_token_stream(), &mut found);
+            }
+        });
+        used.extend(found);
+        if used.len() == len {
+            break;
+        }
+    }
+
+    let retain = |items: &mut Vec<syn::Item>| {
+        items.retain_mut(|item| match item {
+            // The imports of the items removed are removed too, as they would not be resolved.
+            syn::Item::Use(item) => retain_use(&mut item.tree, &declared, &used),
+            item => is_used(item, &declared, &used),
+        })
+    };
+    retain(items);
+    for item in items {
+        if let syn::Item::Mod(syn::ItemMod {
+            content: Some((_, items)),
+            ..
+        }) = item
+        {
+            retain(items);
+        }
+    }
+}
+
+// Removes the names removed from `tree`, and returns whether anything is left.
+fn retain_use(
+    tree: &mut syn::UseTree,
+    declared: &BTreeSet<String>,
+    used: &BTreeSet<String>,
+) -> bool {
+    let name = match tree {
+        syn::UseTree::Path(path) => return retain_use(&mut path.tree, declared, used),
+        syn::UseTree::Group(group) => {
+            let items = std::mem::take(&mut group.items);
+            group.items = items
+                .into_iter()
+                .filter_map(|mut tree| retain_use(&mut tree, declared, used).then_some(tree))
+                .collect();
+            return !group.items.is_empty();
+        }
+        syn::UseTree::Name(name) => name.ident.to_string(),
+        syn::UseTree::Rename(rename) => rename.ident.to_string(),
+        syn::UseTree::Glob(_) => return true,
+    };
+    used.contains(&name) || !declared.contains(&name)
+}
+
+fn for_each_item(items: &[syn::Item], f: &mut impl FnMut(&syn::Item)) {
+    for item in items {
+        match item {
+            syn::Item::Mod(syn::ItemMod {
+                content: Some((_, items)),
+                ..
+            }) => items.iter().for_each(&mut *f),
+            item => f(item),
+        }
+    }
+}
+
+// Returns whether `item` is referred to, or is an impl for a type referred to or of another crate.
+fn is_used(item: &syn::Item, declared: &BTreeSet<String>, used: &BTreeSet<String>) -> bool {
+    let name = match item {
+        syn::Item::Impl(item) => match &*item.self_ty {
+            syn::Type::Path(ty) => ty.path.segments.last().map(|seg| seg.ident.to_string()),
+            _ => None,
+        },
+        item => item_name(item),
+    };
+    match name {
+        Some(name) => used.contains(&name) || !declared.contains(&name),
+        None => true,
+    }
+}
+
+fn item_name(item: &syn::Item) -> Option<String> {
+    let ident = match item {
+        syn::Item::Const(item) => &item.ident,
+        syn::Item::Enum(item) => &item.ident,
+        syn::Item::Fn(item) => &item.sig.ident,
+        syn::Item::Macro(item) => item.ident.as_ref()?,
+        syn::Item::Static(item) => &item.ident,
+        syn::Item::Struct(item) => &item.ident,
+        syn::Item::Trait(item) => &item.ident,
+        syn::Item::Type(item) => &item.ident,
+        _ => return None,
+    };
+    Some(ident.to_string())
+}
+
+fn idents(tokens: proc_macro2::TokenStream, ret: &mut BTreeSet<String>) {
+    for token in tokens {
+        match token {
+            proc_macro2::TokenTree::Ident(ident) => {
+                ret.insert(ident.to_string());
+            }
+            proc_macro2::TokenTree::Group(group) => idents(group.stream(), ret),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,97 @@
+// Compares `ByteScanner`, which searches for the end of each token eight bytes at a time, with
+// the same scanner searching one byte at a time. Run with `cargo bench --bench scan`.
+
+use argio::scan::ByteScanner;
+use std::{hint::black_box, time::Instant};
+
+// `ByteScanner::next_bytes` before it searched eight bytes at a time, with the same bookkeeping.
+struct ByteLoop {
+    buf: Vec<u8>,
+    pos: usize,
+    line_no: usize,
+    line_start: usize,
+    token_pos: (usize, usize),
+}
+
+impl ByteLoop {
+    fn next_bytes(&mut self) -> Option<&[u8]> {
+        while self.pos < self.buf.len() && self.buf[self.pos].is_ascii_whitespace() {
+            if self.buf[self.pos] == b'\n' {
+                self.line_no += 1;
+                self.line_start = self.pos + 1;
+            }
+            self.pos += 1;
+        }
+        if self.pos == self.buf.len() {
+            return None;
+        }
+        self.token_pos = (self.line_no, self.pos - self.line_start + 1);
+        let start = self.pos;
+        self.pos += self.buf[start..]
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .unwrap_or(self.buf.len() - start);
+        Some(&self.buf[start..self.pos])
+    }
+}
+
+fn measure(name: &str, input: &[u8], mut scan: impl FnMut(&[u8]) -> usize) {
+    let mut best = f64::INFINITY;
+    let mut tokens = 0;
+    for _ in 0..5 {
+        let start = Instant::now();
+        tokens = black_box(scan(black_box(input)));
+        best = best.min(start.elapsed().as_secs_f64());
+    }
+    println!(
+        "{:>10}: {:>8} tokens, {:>8.1} MB/s",
+        name,
+        tokens,
+        input.len() as f64 / best / 1e6
+    );
+}
+
+fn main() {
+    // Numbers of up to 19 digits, as in the inputs of competitive programming.
+    let mut x = 1u64;
+    let mut numbers = String::new();
+    for i in 0..2_000_000 {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        numbers += &(x >> (i % 60)).to_string();
+        numbers.push(if i % 10 == 9 { '\n' } else { ' ' });
+    }
+    let strings = (0..200_000)
+        .map(|i| "abcdefghij".repeat(i % 20 + 1))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    for (name, input) in [("numbers", numbers), ("strings", strings)] {
+        println!("{} ({} bytes)", name, input.len());
+        measure("words", input.as_bytes(), |input| {
+            let mut scanner = ByteScanner::new(input);
+            let mut tokens = 0;
+            while let Some(token) = scanner.next_bytes() {
+                black_box(token);
+                tokens += 1;
+            }
+            tokens
+        });
+        measure("bytes", input.as_bytes(), |input| {
+            let mut scanner = ByteLoop {
+                buf: input.to_vec(),
+                pos: 0,
+                line_no: 1,
+                line_start: 0,
+                token_pos: (0, 0),
+            };
+            let mut tokens = 0;
+            while let Some(token) = scanner.next_bytes() {
+                black_box(token);
+                tokens += 1;
+            }
+            tokens
+        });
+    }
+}
@@ -0,0 +1,13 @@
+use argio::{argio, output::AddOne, proconio::marker::Usize1};
+
+// The vertices adjacent to each vertex, read and printed as 1-indexed.
+#[argio(output = AddOne)]
+#[argio::test(input = "3 2\n1 2\n2 3\n", expected = "2\n1 3\n2\n")]
+fn main(n: usize, m: usize, edges: [(Usize1, Usize1); m]) -> Vec<Vec<usize>> {
+    let mut adj = vec![vec![]; n];
+    for (u, v) in edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+    adj
+}
@@ -0,0 +1,15 @@
+use argio::argio;
+
+async fn double(x: i64) -> i64 {
+    x * 2
+}
+
+// The async body is run to completion on `pollster`.
+#[argio(executor = pollster)]
+async fn main(n: usize, a: [i64; n]) -> i64 {
+    let mut sum = 0;
+    for x in a {
+        sum += double(x).await;
+    }
+    sum
+}
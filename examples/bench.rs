@@ -0,0 +1,8 @@
+use argio::argio;
+
+// The time, the memory and the amount of the input and output are printed to stderr.
+#[argio(bench, io_stats)]
+fn main(n: usize) -> usize {
+    let v = vec![1; n];
+    v.into_iter().sum()
+}
@@ -0,0 +1,16 @@
+use argio::{argio, output::BitMask};
+
+// The subset of the items whose weights sum to `w`, as a string of `0` and `1`.
+#[argio(output = BitMask(4))]
+#[argio::test(input = "7\n1 2 4 8\n", expected = "1110\n")]
+fn main(w: u32, a: [u32; 4]) -> u32 {
+    (0..1 << 4)
+        .find(|mask| {
+            (0..4)
+                .filter(|i| mask >> i & 1 == 1)
+                .map(|i| a[i])
+                .sum::<u32>()
+                == w
+        })
+        .unwrap()
+}
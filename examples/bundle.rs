@@ -0,0 +1,12 @@
+use argio::argio;
+use argio::marker::Usize1;
+
+fn max(a: &[i64]) -> i64 {
+    a.iter().copied().max().unwrap()
+}
+
+// `target/bundle.rs` can be submitted to judges that do not have `argio` or `proconio`.
+#[argio(multicase, input = argio::scan, bundle = "target/bundle.rs")]
+fn main(n: usize, k: Usize1, a: [i64; n]) -> i64 {
+    max(&a) + k as i64
+}
@@ -0,0 +1,10 @@
+use argio::argio;
+
+#[argio(multicase, byte_input, strict_input)]
+#[argio::test(
+    input = "2\n3\n1 2 3\n2\n-5 5\n",
+    expected = "Case #1: 6\nCase #2: 0\n"
+)]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.into_iter().sum()
+}
@@ -0,0 +1,14 @@
+use argio::argio;
+
+// The lines printed by a case on its worker thread are also captured by `#[argio::test]`, and
+// nothing but the fallback is captured from a case that timed out.
+#[argio(multicase(format = "Case #{i+1}:\n", case_timeout = "200ms", fallback = -1))]
+#[argio::test(
+    input = "3\n2\n5000\n1\n",
+    expected = "Case #1:\nsleep 2\n2\nCase #2:\n-1\nCase #3:\nsleep 1\n1\n"
+)]
+fn main(n: u64) -> i64 {
+    println!("sleep {}", n);
+    std::thread::sleep(std::time::Duration::from_millis(n));
+    n as i64
+}
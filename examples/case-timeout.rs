@@ -0,0 +1,8 @@
+use argio::argio;
+
+// A case that takes more than a second is abandoned, and -1 is printed instead.
+#[argio(multicase(case_timeout = "1s", fallback = -1))]
+fn main(#[argio(global)] step: u64, n: u64) -> i64 {
+    std::thread::sleep(std::time::Duration::from_millis(n * step));
+    n as i64
+}
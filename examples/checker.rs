@@ -0,0 +1,35 @@
+use argio::argio;
+
+// Any pair of indices `i < j` with `a[i] + a[j] == k` is a correct answer, so the output is
+// verified by `check` instead of comparing with the expected one.
+#[argio(checker = check)]
+#[argio::test(input = "4 5\n1 4 2 3\n", expected = "1 2\n")]
+#[argio::test(input = "2 3\n1 2\n", expected = "1 2\n")]
+fn main(n: usize, k: i64, a: [i64; n]) -> String {
+    for j in 0..n {
+        for i in 0..j {
+            if a[i] + a[j] == k {
+                return format!("{} {}", i + 1, j + 1);
+            }
+        }
+    }
+    unreachable!()
+}
+
+#[allow(dead_code)]
+fn check(input: &str, _expected: &str, actual: &str) -> bool {
+    let input = input
+        .split_whitespace()
+        .map(|s| s.parse::<i64>().unwrap())
+        .collect::<Vec<_>>();
+    let (k, a) = (input[1], &input[2..]);
+    match *actual
+        .split_whitespace()
+        .map(|s| s.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_default()
+    {
+        [i, j] => 1 <= i && i < j && j <= a.len() && a[i - 1] + a[j - 1] == k,
+        _ => false,
+    }
+}
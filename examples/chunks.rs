@@ -0,0 +1,8 @@
+use argio::{argio, output::Chunks};
+
+// The numbers from 1 to `n` in rows of three.
+#[argio(output = Chunks(3))]
+#[argio::test(input = "7\n", expected = "1 2 3\n4 5 6\n7\n")]
+fn main(n: u32) -> Vec<u32> {
+    (1..=n).collect()
+}
@@ -0,0 +1,16 @@
+use argio::{argio, output::Columns};
+
+// For each `i`, the prefix sum and the prefix maximum of `a` up to `i` in a line.
+#[argio(output = Columns)]
+#[argio::test(input = "3\n2 -1 5\n", expected = "2 2\n1 2\n6 5\n")]
+fn main(n: usize, a: [i64; n]) -> (Vec<i64>, Vec<i64>) {
+    let sums = a.iter().scan(0, |s, &x| {
+        *s += x;
+        Some(*s)
+    });
+    let maxs = a.iter().scan(i64::MIN, |m, &x| {
+        *m = (*m).max(x);
+        Some(*m)
+    });
+    (sums.collect(), maxs.collect())
+}
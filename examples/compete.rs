@@ -0,0 +1,9 @@
+use argio::argio;
+
+// The samples and the time limit of the problem are read from the test suite written by
+// `cargo compete new`, which is `testcases/{alias}.yml` by default.
+// Here it is in `examples/testcases/`, and checked by `cargo test --example compete`.
+#[argio(compete = "examples/testcases/compete.yml")]
+fn main(n: usize, a: [f64; n]) -> f64 {
+    a.iter().sum::<f64>() / n as f64
+}
@@ -0,0 +1,7 @@
+#[argio::argio]
+fn main(
+    #[argio(range = 1..=100_000)] n: usize,
+    #[argio(range = -1_000_000_000..=1_000_000_000)] a: [i64; n],
+) -> i64 {
+    a.into_iter().max().unwrap()
+}
@@ -0,0 +1,35 @@
+use argio::{argio, judge::Judge};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+static BEST: AtomicU64 = AtomicU64::new(0);
+
+// Find the largest of `n` hidden numbers by asking `? i`, and answer `! max`. The queries stop
+// when less than 100ms is left, and if the program is stuck anyway, `give_up` answers with the
+// best value so far at the deadline.
+#[argio(interactive, deadline = "1900ms", on_deadline = give_up, judge = judge)]
+fn main(n: usize) {
+    for i in 1..=n {
+        if remaining!() < Duration::from_millis(100) {
+            break;
+        }
+        println!("? {}", i);
+        BEST.fetch_max(read!(u64), Ordering::Relaxed);
+    }
+    println!("{}", give_up());
+}
+
+fn give_up() -> String {
+    format!("! {}", BEST.load(Ordering::Relaxed))
+}
+
+#[allow(dead_code)]
+fn judge(judge: &mut Judge) -> bool {
+    let values = [3, 9, 4];
+    judge.send(values.len());
+    for &value in &values {
+        judge.read_line().unwrap();
+        judge.send(value);
+    }
+    judge.read_line().unwrap() == "! 9"
+}
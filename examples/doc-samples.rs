@@ -0,0 +1,27 @@
+use argio::argio;
+
+/// Prints the sum of the given numbers.
+///
+/// The samples below are checked by `cargo test --example doc-samples`.
+///
+/// ```input
+/// 3
+/// 1 2 3
+/// ```
+///
+/// ```output
+/// 6
+/// ```
+///
+/// ```input
+/// 2
+/// -1 1
+/// ```
+///
+/// ```output
+/// 0
+/// ```
+#[argio]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.into_iter().sum()
+}
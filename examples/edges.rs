@@ -0,0 +1,9 @@
+use argio::{argio, output::Edges};
+
+// A path graph on `n` vertices, printed with 1-indexed vertices.
+#[argio(output = Edges(1))]
+#[argio::test(input = "3\n", expected = "2\n1 2\n2 3\n")]
+#[argio::test(input = "1\n", expected = "0\n")]
+fn main(n: usize) -> Vec<(usize, usize)> {
+    (1..n).map(|v| (v - 1, v)).collect()
+}
@@ -0,0 +1,13 @@
+use argio::argio;
+
+// `solve` can be called directly from tests, and `main` is generated to read the input and print the output.
+#[argio(entry, multicase)]
+fn solve(n: usize, mut k: usize, a: [i64; n]) -> i64 {
+    k %= n;
+    a[k]
+}
+
+#[test]
+fn sample() {
+    assert_eq!(solve(2, 3, vec![1, 2]), 2);
+}
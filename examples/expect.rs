@@ -0,0 +1,28 @@
+use argio::{argio, judge::Judge};
+
+// Find the sum of `n` hidden numbers by asking `? i` for each of them, and answer `! sum`.
+// The judge answers `OK` or `WA` to the answer, and `expect!` panics with the last lines of the
+// interaction on any other response, or on `WA`.
+#[argio(interactive, judge = judge)]
+fn main(n: usize) {
+    let mut sum = 0;
+    for i in 1..=n {
+        println!("? {}", i);
+        sum += read!(i64);
+    }
+    println!("! {}", sum);
+    expect!("OK");
+}
+
+#[allow(dead_code)]
+fn judge(judge: &mut Judge) -> bool {
+    let values = [3, 1, 4];
+    judge.send(values.len());
+    for &value in &values {
+        judge.read_line().unwrap();
+        judge.send(value);
+    }
+    let ok = judge.read_line().unwrap() == "! 8";
+    judge.send(if ok { "OK" } else { "WA" });
+    ok
+}
@@ -0,0 +1,10 @@
+use argio::argio;
+use std::error::Error;
+
+// An error is returned from `main` instead of being printed as an answer.
+#[argio(multicase)]
+fn main(s: String) -> Result<(), Box<dyn Error>> {
+    let n: u64 = s.parse()?;
+    println!("{}", n * n);
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use argio::argio;
+
+// The answers are formatted by itoa and ryu, and checked by
+// `cargo test --features fast-fmt --example fast-fmt`.
+#[argio(multicase)]
+#[argio::test(
+    input = "4\n2\n1 0.5\n2\n3 1e-7\n1\n1e-7\n2\n1e20 -0\n",
+    expected = "Case #1: 1.5\nCase #2: 3.0000001\nCase #3: 0.0000001\nCase #4: 100000000000000000000\n"
+)]
+fn main(n: usize, a: [f64; n]) -> f64 {
+    a.into_iter().sum()
+}
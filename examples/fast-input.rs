@@ -0,0 +1,4 @@
+#[argio::argio(fast_input)]
+fn main(n: usize, x: [u64; n]) -> u64 {
+    x.into_iter().fold(0, |acc, x| acc ^ x)
+}
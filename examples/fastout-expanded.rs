@@ -0,0 +1,30 @@
+use argio::argio;
+
+fn show(x: usize) {
+    println!("{}", x * 2);
+}
+
+// The function as expanded by `#[proconio::fastout]` above `#[argio]`, whose original body is
+// taken back by `argio`, and the output is buffered as with `fastout` below `#[argio]`.
+#[argio(multicase)]
+#[argio::test(input = "2\n1\n2\n", expected = "Case #1: Case #2: ")]
+fn main(n: usize) {
+    let __proconio_stdout = ::std::io::stdout();
+    let mut __proconio_stdout = ::std::io::BufWriter::new(__proconio_stdout.lock());
+    #[allow(unused_macros)]
+    macro_rules! print {
+        ($($tt:tt)*) => {
+            <::std::io::BufWriter<::std::io::StdoutLock> as ::std::io::Write>::write_fmt(
+                &mut __proconio_stdout,
+                format_args!($($tt)*),
+            )
+            .unwrap();
+        };
+    }
+    let __proconio_res = { show(n) };
+    <::std::io::BufWriter<::std::io::StdoutLock> as ::std::io::Write>::flush(
+        &mut __proconio_stdout,
+    )
+    .unwrap();
+    return __proconio_res;
+}
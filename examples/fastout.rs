@@ -0,0 +1,14 @@
+use argio::argio;
+
+fn show(x: usize) {
+    println!("{}", x * 2);
+}
+
+// `#[proconio::fastout]` below `#[argio]` is removed, and the output is buffered instead, so that
+// the lines printed by other functions come first as with `proconio`.
+#[argio(multicase)]
+#[proconio::fastout]
+#[argio::test(input = "2\n1\n2\n", expected = "Case #1: Case #2: ")]
+fn main(n: usize) {
+    show(n)
+}
@@ -0,0 +1,7 @@
+use argio::argio;
+
+// Reads `input.txt` (or the file given by the first argument) and writes the answers to `output.txt`.
+#[argio(files, multicase)]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.iter().sum()
+}
@@ -0,0 +1,9 @@
+use argio::argio;
+
+// The answer is accepted if its absolute or relative error is at most 1e-6.
+#[argio(float_tolerance = 1e-6)]
+#[argio::test(input = "1\n", expected = "3.14159265\n")]
+#[argio::test(input = "100\n", expected = "31415.926535\n")]
+fn main(r: f64) -> f64 {
+    r * r * std::f64::consts::PI
+}
@@ -0,0 +1,12 @@
+use argio::argio;
+
+// Each answer is flushed before the slow part of the next one, so that the progress can be seen.
+#[argio]
+#[argio::test(input = "2\n1 2\n", expected = "1\n4\n")]
+fn main(n: usize, a: [u64; n]) {
+    for x in a {
+        std::thread::sleep(std::time::Duration::from_millis(x));
+        println!("{}", x * x);
+        flush!();
+    }
+}
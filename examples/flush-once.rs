@@ -0,0 +1,9 @@
+use argio::argio;
+
+// The answers are written at once after all the logs.
+#[argio(multicase, flush = once, output_capacity = 1 << 20)]
+#[argio::test(input = "2\n1 2\n3 4\n", expected = "Case #1: 3\nCase #2: 7\n")]
+fn main(a: i64, b: i64) -> i64 {
+    eprintln!("solving {} + {}", a, b);
+    a + b
+}
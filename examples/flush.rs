@@ -0,0 +1,8 @@
+use argio::argio;
+
+// The output is flushed after each case, so that the answers can be seen as soon as they are computed.
+#[argio(multicase, flush = per_case)]
+fn main(n: u64) -> u64 {
+    std::thread::sleep(std::time::Duration::from_millis(n));
+    n
+}
@@ -0,0 +1,13 @@
+use argio::{argio, output::Fraction};
+
+// The probability that the sum of two dice is `s`, as an irreducible fraction.
+#[argio(output = Fraction("/"))]
+#[argio::test(input = "7\n", expected = "1/6\n")]
+#[argio::test(input = "13\n", expected = "0/1\n")]
+fn main(s: u32) -> (u32, u32) {
+    let count = (1..=6)
+        .flat_map(|a| (1..=6).map(move |b| a + b))
+        .filter(|&sum| sum == s)
+        .count();
+    (count as u32, 36)
+}
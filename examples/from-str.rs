@@ -0,0 +1,25 @@
+use std::str::FromStr;
+
+struct Time {
+    h: u32,
+    m: u32,
+}
+
+impl FromStr for Time {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (h, m) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid time: {}", s))?;
+        Ok(Time {
+            h: h.parse().map_err(|_| format!("invalid hour: {}", h))?,
+            m: m.parse().map_err(|_| format!("invalid minute: {}", m))?,
+        })
+    }
+}
+
+#[argio::argio]
+fn main(n: usize, #[argio(from_str)] ts: [Time; n]) -> u32 {
+    ts.iter().map(|t| t.h * 60 + t.m).sum()
+}
@@ -0,0 +1,12 @@
+use argio::random::Rng;
+
+// Prints a random input for the `stress` example.
+// The seed is given by `cargo run --example generator -- <seed>`.
+#[argio::generator]
+fn main(rng: &mut Rng) -> String {
+    let n = rng.range(1..=8);
+    let a = (0..n)
+        .map(|_| rng.range(-10..=10).to_string())
+        .collect::<Vec<_>>();
+    format!("{}\n{}", n, a.join(" "))
+}
@@ -0,0 +1,12 @@
+use argio::argio;
+
+fn show(x: usize) {
+    println!("{}", x * 2);
+}
+
+// The output is not buffered by default, so the lines printed by other functions are printed in
+// order with the headers of the cases.
+#[argio(multicase)]
+fn main(n: usize) {
+    show(n)
+}
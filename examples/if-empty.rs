@@ -0,0 +1,9 @@
+use argio::{argio, output::IfEmpty};
+
+// The divisors of `n` up to `m`, or `-1` if there are none.
+#[argio(output = IfEmpty(-1))]
+#[argio::test(input = "12 4\n", expected = "1 2 3 4\n")]
+#[argio::test(input = "0 3\n", expected = "-1\n")]
+fn main(n: u64, m: u64) -> Vec<u64> {
+    (1..=m).filter(|d| n > 0 && n % d == 0).collect()
+}
@@ -0,0 +1,8 @@
+use argio::argio;
+
+// `cargo run --example input-file-arg -- input.txt` reads the input from `input.txt`.
+#[argio(input_file_arg)]
+#[argio::test(input = "3\n1 2 3\n", expected = "6\n")]
+fn main(n: usize, a: [u64; n]) -> u64 {
+    a.iter().sum()
+}
@@ -0,0 +1,4 @@
+#[argio::argio(input_str = "3\n1 2 3\n", multicase = "")]
+fn main(n: usize) -> usize {
+    n * n
+}
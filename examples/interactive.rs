@@ -0,0 +1,36 @@
+use argio::{argio, judge::Judge};
+
+// Guess a hidden number in `1..=n` by asking `? x`, to which the judge answers `<`, `>` or `=`.
+// The interaction with `judge` is tested by `cargo test --example interactive`.
+#[argio(interactive, judge = judge)]
+fn main(n: u64) {
+    let (mut lo, mut hi) = (1, n);
+    loop {
+        let mid = (lo + hi) / 2;
+        println!("? {}", mid);
+        input! {
+            res: String,
+        }
+        match res.as_str() {
+            "<" => hi = mid - 1,
+            ">" => lo = mid + 1,
+            _ => break,
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn judge(judge: &mut Judge) -> bool {
+    let (n, secret) = (100, 42);
+    judge.send(n);
+    for _ in 0..7 {
+        let query = judge.read_line().unwrap();
+        let x = query.strip_prefix("? ").unwrap().parse::<u64>().unwrap();
+        if x == secret {
+            judge.send("=");
+            return true;
+        }
+        judge.send(if secret < x { "<" } else { ">" });
+    }
+    false
+}
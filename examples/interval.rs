@@ -0,0 +1,21 @@
+use argio::argio;
+
+// Answers the sums of `a[l..=r]` for 1-indexed queries `l r`.
+#[argio(output = argio::output::Join("\n"))]
+#[argio::test(input = "5 3\n1 2 3 4 5\n1 5\n2 3\n4 4\n", expected = "15\n5\n4\n")]
+fn main(n: usize, q: usize, a: [u64; n], #[argio(one_based)] queries: [Interval; q]) -> Vec<u64> {
+    queries
+        .into_iter()
+        .map(|range| a[range].iter().sum())
+        .collect()
+}
+
+// The total length of half-open intervals `[l, r)`.
+#[argio]
+#[argio::test(input = "2\n0 3\n5 7\n", expected = "5\n")]
+fn half_open(n: usize, #[argio(half_open)] intervals: [Interval<i64>; n]) -> i64 {
+    intervals
+        .into_iter()
+        .map(|range| range.end - range.start)
+        .sum()
+}
@@ -0,0 +1,8 @@
+use argio::{argio, output::Join};
+
+// The elements are printed separated by commas instead of spaces.
+#[argio(output = Join(","))]
+#[argio::test(input = "3\n", expected = "0,2,4\n")]
+fn main(n: usize) -> Vec<usize> {
+    (0..n).map(|i| i * 2).collect()
+}
@@ -0,0 +1,12 @@
+use argio::argio;
+
+// The parameters are read from a JSON object, and the return value is written as JSON.
+#[argio(format = json)]
+#[argio::test(
+    input = r#"{"n": 3, "a": [1, 2, 3], "name": "rev"}"#,
+    expected = "[\"rev\",[3,2,1]]\n"
+)]
+#[argio::test(input = r#"{"n": 1, "a": [5]}"#, expected = "[\"\",[5]]\n")]
+fn main(n: usize, a: [i64; n], name: Option<String>) -> (String, Vec<i64>) {
+    (name.unwrap_or_default(), a.into_iter().rev().collect())
+}
@@ -0,0 +1,26 @@
+use argio::argio;
+
+// Greets the people whose names may contain spaces.
+#[argio(output = argio::output::Join("\n"))]
+#[argio::test(
+    input = "2\nAda Lovelace\nAlan Turing\n",
+    expected = "Hello, Ada Lovelace!\nHello, Alan Turing!\n"
+)]
+fn main(n: usize, names: [Line; n]) -> Vec<String> {
+    names
+        .into_iter()
+        .map(|name| format!("Hello, {}!", name))
+        .collect()
+}
+
+// A line can follow tokens on the same line, and sections of lines end at blank lines.
+#[argio(byte_input)]
+#[argio::test(input = "2 the quick brown fox\nabc\nde\n\n", expected = "quick\n5\n")]
+fn words(k: usize, sentence: Line, #[argio(until_blank)] lines: [Line]) -> String {
+    let word = sentence.split(' ').nth(k - 1).unwrap();
+    format!(
+        "{}\n{}",
+        word,
+        lines.iter().map(|line| line.len()).sum::<usize>()
+    )
+}
@@ -0,0 +1,32 @@
+use argio::argio;
+
+mod geometry {
+    use std::str::FromStr;
+
+    // A line `y = ax + b` given as `a:b`, which has the same name as the marker `Line`.
+    pub struct Line {
+        pub a: i64,
+        pub b: i64,
+    }
+
+    impl FromStr for Line {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (a, b) = s.split_once(':').unwrap_or((s, "0"));
+            Ok(Line {
+                a: a.parse()?,
+                b: b.parse()?,
+            })
+        }
+    }
+}
+
+// Only the markers written as they are or under `marker` are recognized by name, so
+// `geometry::Line` is read as a token, while `argio::marker::Line` reads the rest of the line.
+#[argio]
+#[argio::test(input = "2 1:2 3:-1 0\nthe x\n", expected = "the x: 1\n")]
+fn main(n: usize, lines: [geometry::Line; n], x: i64, name: argio::marker::Line) -> String {
+    let y = lines.iter().map(|line| line.a * x + line.b).sum::<i64>();
+    format!("{}: {}", name, y)
+}
@@ -0,0 +1,9 @@
+use argio::argio;
+
+// The heap memory used for each sample is checked by `cargo test --example memory-limit`.
+#[argio(memory_limit = "1MB")]
+#[argio::test(input = "1000\n", expected = "499500\n")]
+#[argio::test(input = "100000\n", expected = "4999950000\n")]
+fn main(n: u64) -> u64 {
+    (0..n).sum()
+}
@@ -0,0 +1,8 @@
+use argio::argio;
+
+// The header and the answer are printed together as one line after each case.
+#[argio(multicase(case_format = "Case #{i+1}: {ret}"))]
+fn main(a: i64, b: i64) -> i64 {
+    eprintln!("solving {} + {}", a, b);
+    a + b
+}
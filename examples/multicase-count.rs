@@ -0,0 +1,5 @@
+// The number of cases can be changed by the environment variable `CASES`.
+#[argio::argio(multicase(count = std::env::var("CASES").map_or(1, |s| s.parse().unwrap())))]
+fn main(n: usize) -> usize {
+    n * 2
+}
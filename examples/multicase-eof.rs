@@ -0,0 +1,4 @@
+#[argio::argio(multicase(eof, format = ""))]
+fn main(a: i64, b: i64) -> i64 {
+    a + b
+}
@@ -0,0 +1,7 @@
+use argio::argio;
+
+// The first line is "T MOD", and the modulo is shared by all cases.
+#[argio(multicase(first_line = "t: usize, modulo: u64"))]
+fn main(a: u64, b: u64) -> u64 {
+    a * b % modulo
+}
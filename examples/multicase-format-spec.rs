@@ -0,0 +1,9 @@
+use argio::argio;
+
+// The header can have several placeholders with format specs, and braces are escaped by doubling
+// them. `::` in a placeholder is a part of a path, not the start of a format spec.
+// With the input `2 3 5`, this prints `{01/2} 3: 6` and `{02/2} 5: 10`.
+#[argio(multicase = "{{{i + 1:02}/{cases}}} {usize::MAX.min(n)}: ")]
+fn main(n: usize) -> usize {
+    n * 2
+}
@@ -0,0 +1,7 @@
+use argio::argio;
+
+// The first line holds the modulo shared by all cases, followed by the number of cases.
+#[argio(multicase)]
+fn main(#[argio(global)] modulo: u64, a: u64, b: u64) -> u64 {
+    (a * b) % modulo
+}
@@ -0,0 +1,7 @@
+use argio::argio;
+
+// The parameters of each case can be used in its header.
+#[argio(multicase = "Case #{i+1} (n={n}): ")]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.iter().sum()
+}
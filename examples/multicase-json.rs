@@ -0,0 +1,15 @@
+use argio::argio;
+
+// Prints each case as a JSON-ish line, e.g. `{"case": 1, "answer": 3}`.
+#[argio(multicase = "{{\"case\": {i+1}, \"answer\": ", output = Json)]
+fn main(a: i64, b: i64) -> i64 {
+    a + b
+}
+
+struct Json(i64);
+
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}}}", self.0)
+    }
+}
@@ -0,0 +1,11 @@
+use argio::argio;
+
+// The header is printed on its own line by `case_header!()`, followed by the grid.
+#[argio(multicase(format = "Case #{case}:\n", header = manual))]
+fn main(h: usize, w: usize) {
+    case_header!();
+    for y in 0..h {
+        let row = (0..w).map(|x| if (x + y) % 2 == 0 { '#' } else { '.' });
+        println!("{}", row.collect::<String>());
+    }
+}
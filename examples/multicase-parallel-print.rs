@@ -0,0 +1,19 @@
+use argio::argio;
+
+// Prints the steps of a case from outside the function body. The output of each case is kept
+// in its own buffer, so that the lines are not mixed up with the other cases.
+fn show_steps(n: u64) {
+    for i in 1..=n {
+        argio::io::println(format_args!("step {}", i));
+    }
+}
+
+#[argio(multicase(format = "Case #{i+1}:\n", parallel))]
+#[argio::test(
+    input = "3\n2\n0\n1\n",
+    expected = "Case #1:\nstep 1\nstep 2\n2\nCase #2:\n0\nCase #3:\nstep 1\n1\n"
+)]
+fn main(n: u64) -> u64 {
+    show_steps(n);
+    n
+}
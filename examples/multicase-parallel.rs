@@ -0,0 +1,16 @@
+use argio::argio;
+
+// Heavy cases are solved in parallel, and the outputs are printed in the order of the cases.
+#[argio(multicase(parallel))]
+#[argio::test(
+    input = "5\n1\n2\n3\n4\n5\n",
+    expected = "Case #1: 11400714819323198485
+Case #2: 11698534113733411903
+Case #3: 8718942387164016640
+Case #4: 9571257329123412
+Case #5: 1672913115632397373
+"
+)]
+fn main(n: u64) -> u64 {
+    (1..=n).fold(0, |acc, i| acc ^ i.wrapping_mul(0x9e3779b97f4a7c15))
+}
@@ -0,0 +1,7 @@
+use argio::argio;
+
+// All the cases are read first, and each case is answered with its rank among all the cases.
+#[argio(multicase(read_all = all))]
+fn main(a: i64, b: i64) -> usize {
+    all.iter().filter(|(c, d)| c + d < a + b).count() + 1
+}
@@ -0,0 +1,7 @@
+use argio::argio;
+
+// A blank line is printed between the cases, but not after the last one.
+#[argio(multicase(case_separator = "\n"))]
+fn main(n: usize) -> usize {
+    n * 2
+}
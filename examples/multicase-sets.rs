@@ -0,0 +1,7 @@
+use argio::argio;
+
+// The input consists of test sets, each of which contains its own cases.
+#[argio(sets = "Set #{i+1}:\n", multicase = "  Case #{i+1}: ")]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.iter().sum()
+}
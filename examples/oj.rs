@@ -0,0 +1,8 @@
+use argio::argio;
+
+// The samples downloaded by `oj download` into `test/` are checked by `#[argio(oj)]`.
+// Here they are in `examples/oj/`, and checked by `cargo test --example oj`.
+#[argio(oj = "examples/oj")]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.into_iter().sum()
+}
@@ -0,0 +1,19 @@
+use argio::argio;
+
+// `TIME_LIMIT_MS=500 cargo run --example options -- --seed 42 --temperature=2.5 < input.txt`
+#[argio(multicase)]
+#[argio::test(input = "2\n1 2\n3 4\n", expected = "Case #1: 3\nCase #2: 7\n")]
+fn main(
+    #[argio(arg, default = 0)] seed: u64,
+    #[argio(arg, default = 1.0)] temperature: f64,
+    #[argio(arg)] max_iters: Option<usize>,
+    #[argio(env = "TIME_LIMIT_MS", default = 2000)] time_limit_ms: u64,
+    a: i64,
+    b: i64,
+) -> i64 {
+    eprintln!(
+        "seed = {}, temperature = {}, max_iters = {:?}, time_limit_ms = {}",
+        seed, temperature, max_iters, time_limit_ms
+    );
+    a + b
+}
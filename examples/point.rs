@@ -0,0 +1,12 @@
+use argio::{argio, marker::Point};
+
+// Twice the area of a polygon given by its vertices in order.
+#[argio]
+#[argio::test(input = "4\n0 0\n2 0\n2 2\n0 2\n", expected = "8\n")]
+#[argio::test(input = "3\n0 0\n0 3\n4 0\n", expected = "12\n")]
+fn main(n: usize, p: [Point<i64>; n]) -> i64 {
+    (0..n)
+        .map(|i| p[i].cross(p[(i + 1) % n]))
+        .sum::<i64>()
+        .abs()
+}
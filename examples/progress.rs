@@ -0,0 +1,7 @@
+use argio::argio;
+
+// The number of finished cases is shown on stderr while running.
+#[argio(multicase, progress)]
+fn main(n: u64) -> u64 {
+    (1..=n).fold(0, |acc, i| acc ^ i.wrapping_mul(0x9e3779b97f4a7c15))
+}
@@ -0,0 +1,35 @@
+use argio::argio;
+
+// Random inputs are made from the parameters, and `sorted` is checked for each output by
+// `cargo test --example property`.
+#[argio(output = Wrap)]
+#[argio::property(check = sorted, iters = 1000)]
+fn main(#[argio(range = 0..=20)] n: usize, #[argio(range = -100..=100)] a: [i64; n]) -> Vec<i64> {
+    let mut a = a;
+    a.sort_unstable();
+    a
+}
+
+#[allow(dead_code)]
+fn sorted(input: &str, output: &str) -> bool {
+    let mut input = input
+        .split_whitespace()
+        .skip(1)
+        .map(|s| s.parse::<i64>().unwrap())
+        .collect::<Vec<_>>();
+    let output = output
+        .split_whitespace()
+        .map(|s| s.parse::<i64>().unwrap())
+        .collect::<Vec<_>>();
+    input.sort();
+    input == output
+}
+
+struct Wrap(Vec<i64>);
+
+impl std::fmt::Display for Wrap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let a = self.0.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        write!(f, "{}", a.join(" "))
+    }
+}
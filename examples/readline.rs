@@ -0,0 +1,21 @@
+use argio::argio;
+
+// Commands of the form `add <x>` or `echo <text>`, read one by one in the body.
+#[argio]
+#[argio::test(
+    input = "4\nadd 3\necho hello world\nadd 4\necho bye\n",
+    expected = "hello world\nbye\n7\n"
+)]
+fn main(q: usize) -> u64 {
+    let mut sum = 0;
+    for _ in 0..q {
+        let command = read!(String);
+        if command == "add" {
+            sum += read!(u64);
+        } else {
+            println!("{}", readline!().unwrap());
+        }
+    }
+    assert_eq!(readline!(), None);
+    sum
+}
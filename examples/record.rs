@@ -0,0 +1,8 @@
+use argio::argio;
+
+// Each input given to `cargo run --example record` is recorded in `examples/records`, and the
+// recorded outputs are checked by `cargo test --example record`.
+#[argio(record = "examples/records")]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.into_iter().filter(|x| x % 2 == 0).sum()
+}
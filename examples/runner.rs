@@ -0,0 +1,12 @@
+use argio::argio;
+
+// `run` runs the whole program with a string as its input, so the samples can be checked in tests.
+#[argio(multicase, runner = run)]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.into_iter().max().unwrap()
+}
+
+#[test]
+fn sample() {
+    assert_eq!(run("2\n3\n1 3 2\n1\n-5\n"), "Case #1: 3\nCase #2: -5\n");
+}
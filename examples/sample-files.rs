@@ -0,0 +1,8 @@
+use argio::argio;
+
+// Each `examples/samples/*.in` is checked against the `.out` file of the same name
+// by `cargo test --example sample-files`.
+#[argio(tests = "examples/samples/*.in")]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.into_iter().sum()
+}
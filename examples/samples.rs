@@ -0,0 +1,9 @@
+use argio::argio;
+
+// The samples of the problem are checked by `cargo test --example samples`.
+#[argio::test(input = "2\n3\n1 2 3\n1\n5\n", expected = "Case #1: 6\nCase #2: 5\n")]
+#[argio(multicase)]
+#[argio::test(input = "1\n2\n-1 1\n", expected = "Case #1: 0\n")]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.into_iter().sum()
+}
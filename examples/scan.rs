@@ -0,0 +1,8 @@
+use argio::marker::Chars;
+
+#[argio::argio(input = argio::scan)]
+fn main(n: usize, s: [Chars; n]) -> usize {
+    s.iter()
+        .filter(|s| s == &&s.iter().rev().cloned().collect::<Vec<_>>())
+        .count()
+}
@@ -0,0 +1,19 @@
+use argio::{argio, random::Rng};
+
+// The expected outputs are kept in `examples/snapshots`, and they are written by
+// `ARGIO_UPDATE=1 cargo test --example snapshot`.
+#[argio(snapshot = "examples/snapshots")]
+#[argio::test(input = "3\n1 2 3\n")]
+#[argio::stress(gen = gen, iters = 20)]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    a.into_iter().map(|x| x * x).sum()
+}
+
+#[argio::generator]
+fn gen(rng: &mut Rng) -> String {
+    let n = rng.range(1..=5);
+    let a = (0..n)
+        .map(|_| rng.range(-10..=10).to_string())
+        .collect::<Vec<_>>();
+    format!("{}\n{}\n", n, a.join(" "))
+}
@@ -0,0 +1,23 @@
+use argio::argio;
+
+fn depth(v: usize, children: &[Vec<usize>]) -> usize {
+    children[v]
+        .iter()
+        .map(|&u| depth(u, children) + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+// A deep recursion on a path graph does not overflow the stack.
+#[argio(stack_size = "512MB", runner = run)]
+fn main(n: usize) -> usize {
+    let children = (0..n)
+        .map(|v| if v + 1 < n { vec![v + 1] } else { vec![] })
+        .collect::<Vec<_>>();
+    depth(0, &children)
+}
+
+#[test]
+fn deep() {
+    assert_eq!(run("1000000\n"), "999999\n");
+}
@@ -0,0 +1,34 @@
+use argio::{argio, random::Rng};
+
+// `main` is compared with `brute` on random inputs by `cargo test --example stress`.
+#[argio]
+#[argio::stress(gen = gen, brute = brute, iters = 1000)]
+fn main(n: usize, a: [i64; n]) -> i64 {
+    // The maximum sum of a non-empty subarray
+    let mut ret = i64::MIN;
+    let mut cur = 0;
+    for x in a {
+        cur = x.max(cur + x);
+        ret = ret.max(cur);
+    }
+    ret
+}
+
+#[allow(dead_code)]
+#[argio]
+fn brute(n: usize, a: [i64; n]) -> i64 {
+    (0..n)
+        .flat_map(|i| (i + 1..=n).map(move |j| (i, j)))
+        .map(|(i, j)| a[i..j].iter().sum())
+        .max()
+        .unwrap()
+}
+
+#[argio::generator]
+fn gen(rng: &mut Rng) -> String {
+    let n = rng.range(1..=8);
+    let a = (0..n)
+        .map(|_| rng.range(-10..=10).to_string())
+        .collect::<Vec<_>>();
+    format!("{}\n{}\n", n, a.join(" "))
+}
@@ -0,0 +1,8 @@
+use argio::argio;
+
+// `ARGIO_OUTPUT=out.txt cargo run --example tee < input.txt` shows the answers on stderr as well.
+#[argio(multicase, tee = "> ")]
+#[argio::test(input = "2\n1 2\n3 4\n", expected = "Case #1: 3\nCase #2: 7\n")]
+fn main(a: i64, b: i64) -> i64 {
+    a + b
+}
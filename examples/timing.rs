@@ -0,0 +1,7 @@
+use argio::argio;
+
+// The elapsed time of each case and the summary are printed to stderr.
+#[argio(multicase, timing)]
+fn main(n: u64) -> u64 {
+    (1..=n).fold(0, |acc, i| acc ^ i.wrapping_mul(0x9e3779b97f4a7c15))
+}
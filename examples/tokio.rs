@@ -0,0 +1,14 @@
+use argio::argio;
+
+// The body runs on a Tokio runtime, so tasks can be spawned in it.
+#[argio(multicase, runtime = tokio)]
+async fn main(n: u64) -> u64 {
+    let tasks = (0..4)
+        .map(|k| argio::tokio::spawn(async move { (1..=n).filter(|i| i % 4 == k).sum::<u64>() }))
+        .collect::<Vec<_>>();
+    let mut sum = 0;
+    for task in tasks {
+        sum += task.await.unwrap();
+    }
+    sum
+}
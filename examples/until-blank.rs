@@ -0,0 +1,26 @@
+use argio::argio;
+
+// Two sections separated by a blank line: the rules `a b` and the queries.
+#[argio]
+#[argio::test(input = "1 2\n2 3\n\n1\n3\n4\n", expected = "2\n")]
+#[argio::test(input = "\n5\n", expected = "1\n")]
+fn main(#[argio(until_blank)] rules: [(u32, u32)], #[argio(until_blank)] queries: [u32]) -> usize {
+    queries
+        .iter()
+        .filter(|&&q| rules.iter().all(|&(a, _)| a != q))
+        .count()
+}
+
+// Sections of any number of lines, even with several blank lines between them.
+#[argio(byte_input)]
+#[argio::test(input = "1 2\n3\n\n\n4\n\n5 6\n", expected = "11\n")]
+fn largest(
+    #[argio(until_blank)] a: [u64],
+    #[argio(until_blank)] b: [u64],
+    #[argio(until_blank)] c: [u64],
+) -> u64 {
+    a.iter()
+        .sum::<u64>()
+        .max(b.iter().sum())
+        .max(c.iter().sum())
+}
@@ -0,0 +1,14 @@
+use argio::argio;
+
+// The items are read as the weights and the values in separate vectors.
+#[argio]
+#[argio::test(input = "3 5\n2 3\n3 4\n4 5\n", expected = "7\n")]
+fn main(n: usize, cap: usize, (w, v): Unzip<[(usize, u64); n]>) -> u64 {
+    let mut dp = vec![0; cap + 1];
+    for i in 0..n {
+        for c in (w[i]..=cap).rev() {
+            dp[c] = dp[c].max(dp[c - w[i]] + v[i]);
+        }
+    }
+    dp[cap]
+}
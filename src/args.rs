@@ -0,0 +1,100 @@
+//! Command line options and environment variables for the parameters with `#[argio(arg)]` and
+//! `#[argio(env = "NAME")]`.
+
+use std::{env, ffi::OsString, fmt::Debug, str::FromStr, sync::OnceLock};
+
+static PARSED: OnceLock<Parsed> = OnceLock::new();
+
+struct Parsed {
+    options: Vec<(String, String)>,
+    positional: Vec<OsString>,
+}
+
+/// Parses the command line for the options `names`, given as `--name value` or `--name=value`.
+///
+/// The other arguments, such as the path of the input file, are left as positional arguments, and `--` ends the options. Nothing is parsed while the output is captured by [`io::run`](crate::io::run), as the command line is the one of the test harness.
+pub fn parse(names: &[&str]) {
+    if crate::io::captured() {
+        return;
+    }
+    PARSED.get_or_init(|| {
+        let mut ret = Parsed {
+            options: vec![],
+            positional: vec![],
+        };
+        let mut args = env::args_os().skip(1);
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                ret.positional.extend(args);
+                break;
+            }
+            let option = match arg.to_str().and_then(|arg| arg.strip_prefix("--")) {
+                Some(option) => option.to_string(),
+                None => {
+                    ret.positional.push(arg);
+                    continue;
+                }
+            };
+            let (name, value) = match option.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (option, None),
+            };
+            if !names.contains(&name.as_str()) {
+                let names = names.iter().map(|name| format!("--{}", name));
+                panic!(
+                    "unknown option `--{}`; the options are {}",
+                    name,
+                    names.collect::<Vec<_>>().join(", ")
+                );
+            }
+            let value = match value {
+                Some(value) => value,
+                None => match args.next().and_then(|value| value.into_string().ok()) {
+                    Some(value) => value,
+                    None => panic!("option `--{}` requires a value", name),
+                },
+            };
+            ret.options.push((name, value));
+        }
+        ret
+    });
+}
+
+/// Returns the value of the option `name` parsed as `T`, or `None` if it is not given.
+///
+/// If the option is given more than once, the last one is used.
+pub fn value<T: FromStr>(name: &str) -> Option<T>
+where
+    T::Err: Debug,
+{
+    let parsed = PARSED.get()?;
+    let (_, value) = parsed.options.iter().rev().find(|(n, _)| n == name)?;
+    Some(crate::parse_token(name, value))
+}
+
+/// Returns the value of the environment variable `name` parsed as `T`, or `None` if it is not set.
+pub fn var<T: FromStr>(name: &str) -> Option<T>
+where
+    T::Err: Debug,
+{
+    let value = env::var(name).ok()?;
+    Some(crate::parse_token(name, &value))
+}
+
+/// Panics for the option `name` which is required but not given.
+pub fn missing(name: &str) -> ! {
+    panic!("option `--{}` is required", name)
+}
+
+/// Panics for the environment variable `name` which is required but not set.
+pub fn missing_var(name: &str) -> ! {
+    panic!("environment variable `{}` is required", name)
+}
+
+// Returns the positional arguments, which are all the arguments unless options are parsed.
+pub(crate) fn positional() -> Vec<OsString> {
+    match PARSED.get() {
+        Some(parsed) => parsed.positional.clone(),
+        None => env::args_os().skip(1).collect(),
+    }
+}
@@ -0,0 +1,57 @@
+//! Alternative input backends.
+//!
+//! Each backend provides an `input!` macro that accepts the same syntax as [`scan!`](crate::scan), so it can be selected with `#[argio(input = argio::backend::<name>::input)]`. Only the tokenization is delegated to the backend; the signature is translated by the common grammar of `scan!`.
+
+use crate::scan::Source;
+
+#[cfg(feature = "text_io")]
+pub mod text_io;
+#[cfg(feature = "whiteread")]
+pub mod whiteread;
+
+/// A raw `BufRead` token scanner.
+///
+/// This is the same as the built-in backend.
+pub mod bufread {
+    pub use crate::scan as input;
+}
+
+/// A backend that reads one token at a time.
+pub trait ReadToken {
+    /// Returns the next token, or `None` if the input is exhausted.
+    fn read_token(&mut self) -> Option<String>;
+}
+
+/// A [`Source`] adapter for [`ReadToken`] backends.
+pub struct Tokens<R> {
+    inner: R,
+    peeked: Option<String>,
+    token: String,
+}
+
+impl<R: ReadToken> Tokens<R> {
+    pub fn new(inner: R) -> Self {
+        Tokens {
+            inner,
+            peeked: None,
+            token: String::new(),
+        }
+    }
+}
+
+impl<R: ReadToken> Source for Tokens<R> {
+    fn next_token(&mut self) -> Option<&str> {
+        self.token = match self.peeked.take() {
+            Some(token) => token,
+            None => self.inner.read_token()?,
+        };
+        Some(&self.token)
+    }
+
+    fn is_empty(&mut self) -> bool {
+        if self.peeked.is_none() {
+            self.peeked = self.inner.read_token();
+        }
+        self.peeked.is_none()
+    }
+}
@@ -0,0 +1,35 @@
+//! A backend using [`text_io`](https://crates.io/crates/text_io).
+
+use super::{ReadToken, Tokens};
+
+/// Reads tokens from the standard input with `text_io::read!`.
+pub struct TextIo;
+
+impl ReadToken for TextIo {
+    fn read_token(&mut self) -> Option<String> {
+        let token: String = text_io::read!();
+        if token.is_empty() {
+            None
+        } else {
+            Some(token)
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn stdin() -> Tokens<TextIo> {
+    Tokens::new(TextIo)
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __text_io_input {
+    (from $source:expr, $($rest:tt)*) => {
+        $crate::scan!(from $source, $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        $crate::scan!(from &mut $crate::backend::text_io::stdin(), $($rest)*);
+    };
+}
+
+pub use crate::__text_io_input as input;
@@ -0,0 +1,43 @@
+//! A backend using [`whiteread`](https://crates.io/crates/whiteread).
+
+use super::{ReadToken, Tokens};
+use std::{
+    io::{BufRead, BufReader, Stdin},
+    sync::{Mutex, MutexGuard, OnceLock},
+};
+use whiteread::Reader;
+
+impl<B: BufRead> ReadToken for Reader<B> {
+    fn read_token(&mut self) -> Option<String> {
+        match self.continue_::<String>() {
+            Ok(token) => Some(token),
+            Err(err) if err.as_ref().is_too_short() => None,
+            Err(err) => panic!("failed to read the input: {}", err),
+        }
+    }
+}
+
+static STDIN: OnceLock<Mutex<Tokens<Reader<BufReader<Stdin>>>>> = OnceLock::new();
+
+#[doc(hidden)]
+pub fn stdin() -> MutexGuard<'static, Tokens<Reader<BufReader<Stdin>>>> {
+    STDIN
+        .get_or_init(|| Mutex::new(Tokens::new(Reader::from_stdin_naive())))
+        .lock()
+        .unwrap()
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __whiteread_input {
+    (from $source:expr, $($rest:tt)*) => {
+        $crate::scan!(from $source, $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        let mut locked_stdin = $crate::backend::whiteread::stdin();
+        $crate::scan!(from &mut *locked_stdin, $($rest)*);
+        drop(locked_stdin);
+    };
+}
+
+pub use crate::__whiteread_input as input;
@@ -0,0 +1,29 @@
+//! Runtime checks for the parameter constraints.
+
+use std::{fmt::Debug, ops::RangeBounds};
+
+pub fn range<T, R>(name: &str, index: &[usize], value: &T, range: &R)
+where
+    T: PartialOrd + Debug,
+    R: RangeBounds<T> + Debug,
+{
+    if !range.contains(value) {
+        let index = index.iter().map(|i| format!("[{}]", i)).collect::<String>();
+        panic!(
+            "parameter `{}{}` = {:?} is out of range {:?}",
+            name, index, value, range
+        );
+    }
+}
+
+pub fn len<R>(name: &str, len: usize, range: &R)
+where
+    R: RangeBounds<usize> + Debug,
+{
+    if !range.contains(&len) {
+        panic!(
+            "length of parameter `{}` = {} is out of range {:?}",
+            name, len, range
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Diagnostics for input errors, and the messages of argio on stderr.
+//!
+//! The generated code records which parameter is being read, and a panic hook appends it to the panic message.
+
+use std::{
+    cell::Cell,
+    env, fmt,
+    io::{self, IsTerminal},
+    panic,
+    sync::{Once, OnceLock},
+};
+
+#[derive(Clone, Copy)]
+struct Context {
+    param: &'static str,
+    case: Option<usize>,
+}
+
+thread_local! {
+    static CONTEXT: Cell<Option<Context>> = const { Cell::new(None) };
+    static POSITION: Cell<Option<(usize, usize)>> = const { Cell::new(None) };
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Installs the panic hook that reports the context of input errors.
+pub fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let prev = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if QUIET.with(Cell::get) {
+                return;
+            }
+            crate::io::flush_on_panic();
+            prev(info);
+            if let Some(note) = note() {
+                message(Level::Error, format_args!("{}", note));
+            }
+        }));
+    });
+}
+
+/// Runs `f` without reporting panics on this thread.
+///
+/// This is used to run the program on many inputs which are expected to fail.
+pub fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    install();
+    let prev = QUIET.with(|q| q.replace(true));
+    let ret = f();
+    QUIET.with(|q| q.set(prev));
+    ret
+}
+
+/// Marks that the parameter `param` is being read.
+pub fn enter(param: &'static str, case: Option<usize>) {
+    CONTEXT.with(|c| c.set(Some(Context { param, case })));
+}
+
+/// Marks that reading the parameter has finished.
+pub fn leave() {
+    CONTEXT.with(|c| c.set(None));
+}
+
+pub(crate) fn set_position(pos: (usize, usize)) {
+    POSITION.with(|p| p.set(Some(pos)));
+}
+
+fn note() -> Option<String> {
+    let context = CONTEXT.with(|c| c.get())?;
+    let mut ret = format!("while reading `{}`", context.param);
+    if let Some(case) = context.case {
+        ret += &format!(" of case #{}", case + 1);
+    }
+    if let Some((line, column)) = POSITION.with(|p| p.get()) {
+        ret += &format!(" (around line {}, column {})", line, column);
+    }
+    Some(ret)
+}
+
+/// The kind of a message, which decides its color.
+#[derive(Clone, Copy)]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Writes a message of argio to stderr, tagged with `argio:`.
+///
+/// When stderr is a terminal, the tag is colored by `level` and the parts of the message in backquotes are made bold. The environment variable `NO_COLOR` turns the colors off.
+pub fn message(level: Level, args: fmt::Arguments) {
+    if colored() {
+        eprintln!("{} {}", tag(level), emphasize(&args.to_string()));
+    } else {
+        eprintln!("argio: {}", args);
+    }
+}
+
+// Returns the tag of messages, colored if stderr is a terminal.
+pub(crate) fn tag(level: Level) -> &'static str {
+    if !colored() {
+        return "argio:";
+    }
+    match level {
+        Level::Info => "\x1b[1;36margio:\x1b[0m",
+        Level::Warning => "\x1b[1;33margio:\x1b[0m",
+        Level::Error => "\x1b[1;31margio:\x1b[0m",
+    }
+}
+
+fn colored() -> bool {
+    static COLORED: OnceLock<bool> = OnceLock::new();
+    *COLORED.get_or_init(|| io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none())
+}
+
+fn emphasize(msg: &str) -> String {
+    let mut ret = String::new();
+    for (i, part) in msg.split('`').enumerate() {
+        if i % 2 == 0 {
+            ret += part;
+        } else {
+            ret += &format!("\x1b[1m`{}`\x1b[0m", part);
+        }
+    }
+    ret
+}
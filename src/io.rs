@@ -0,0 +1,883 @@
+//! Input and output handles used by the generated code.
+
+use crate::{
+    args,
+    diag::{self, Level},
+};
+use std::{
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, VecDeque},
+    env,
+    ffi::OsString,
+    fmt,
+    fs::{self, File},
+    hash::{Hash, Hasher},
+    io::{self, BufRead, BufReader, BufWriter, Cursor, Read, Write},
+    net::TcpStream,
+    panic,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError, Sender},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    static INPUT: RefCell<Option<Box<dyn BufRead + Send>>> = const { RefCell::new(None) };
+    static CAPTURE: RefCell<Option<Capture>> = const { RefCell::new(None) };
+    static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+// The destination of the output given by `run` or `run_with`.
+enum Capture {
+    Buffer(String),
+    Channel(Sender<String>),
+}
+
+/// Runs `f` with `input` as its input, and returns its output.
+///
+/// This is used by the function generated by `#[argio(runner)]`.
+pub fn run(input: &str, f: impl FnOnce()) -> String {
+    let input = Cursor::new(input.as_bytes().to_vec());
+    match run_capture(Box::new(input), Capture::Buffer(String::new()), f) {
+        Some(Capture::Buffer(output)) => output,
+        _ => String::new(),
+    }
+}
+
+/// Runs `f` with `input` as its input, and sends each piece of its output to `output`.
+///
+/// This is used to run interactive programs against a judge.
+pub(crate) fn run_with(input: Box<dyn BufRead + Send>, output: Sender<String>, f: impl FnOnce()) {
+    run_capture(input, Capture::Channel(output), f);
+}
+
+fn run_capture(
+    input: Box<dyn BufRead + Send>,
+    output: Capture,
+    f: impl FnOnce(),
+) -> Option<Capture> {
+    // Restores the normal input and output even if `f` panics.
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            INPUT.with(|i| *i.borrow_mut() = None);
+            CAPTURE.with(|c| *c.borrow_mut() = None);
+        }
+    }
+
+    INPUT.with(|i| *i.borrow_mut() = Some(input));
+    CAPTURE.with(|c| *c.borrow_mut() = Some(output));
+    let _reset = Reset;
+    f();
+    CAPTURE.with(|c| c.borrow_mut().take())
+}
+
+/// Runs `f` on a new thread with a stack of `size` bytes, and waits for it to finish.
+///
+/// This is used by the function generated by `#[argio(stack_size)]`. The input and the output given by [`run`] are carried over to the thread, and a panic in the thread is propagated to the caller.
+pub fn spawn_with_stack<T: Send>(size: usize, f: impl FnOnce() -> T + Send) -> T {
+    let input = INPUT.with(|i| i.borrow_mut().take());
+    let capture = CAPTURE.with(|c| c.borrow_mut().take());
+    let ret = thread::scope(|scope| {
+        let handle = thread::Builder::new()
+            .stack_size(size)
+            .spawn_scoped(scope, move || {
+                INPUT.with(|i| *i.borrow_mut() = input);
+                CAPTURE.with(|c| *c.borrow_mut() = capture);
+                let ret = f();
+                (ret, CAPTURE.with(|c| c.borrow_mut().take()))
+            })
+            .unwrap_or_else(|err| panic!("failed to spawn a thread: {}", err));
+        handle.join()
+    });
+    match ret {
+        Ok((ret, capture)) => {
+            CAPTURE.with(|c| *c.borrow_mut() = capture);
+            ret
+        }
+        Err(err) => panic::resume_unwind(err),
+    }
+}
+
+/// Runs `f` on a new thread, and waits for it to finish for at most `timeout`.
+///
+/// This is used by the function generated by `#[argio(multicase(case_timeout))]`. The input and the output given by [`run`] are carried over to the thread. The captured output of `f` is kept until it finishes, so that nothing is captured from a case which timed out. An error is returned if `f` timed out or panicked.
+pub fn spawn_with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, RecvTimeoutError> {
+    let input = INPUT.with(|i| i.borrow_mut().take());
+    let carried = CAPTURE.with(|c| match c.borrow().as_ref() {
+        Some(Capture::Buffer(_)) => Some(Capture::Buffer(String::new())),
+        Some(Capture::Channel(tx)) => Some(Capture::Channel(tx.clone())),
+        None => None,
+    });
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        INPUT.with(|i| *i.borrow_mut() = input);
+        CAPTURE.with(|c| *c.borrow_mut() = carried);
+        let ret = f();
+        let input = INPUT.with(|i| i.borrow_mut().take());
+        let carried = CAPTURE.with(|c| c.borrow_mut().take());
+        let _ = tx.send((ret, input, carried));
+    });
+    let (ret, input, carried) = rx.recv_timeout(timeout)?;
+    INPUT.with(|i| *i.borrow_mut() = input);
+    if let Some(Capture::Buffer(output)) = carried {
+        capture(|buf| fmt::Write::write_str(buf, &output));
+    }
+    Ok(ret)
+}
+
+/// Runs `f` with its output written to a buffer, and returns the value of `f` and the output.
+///
+/// This is used by the function generated by `#[argio(multicase(parallel))]`, whose cases are solved on the threads of `rayon` and printed in the order of the cases.
+pub fn buffered<T>(f: impl FnOnce() -> T) -> (T, String) {
+    // Restores the output of the thread even if `f` panics, as it may be reused by `rayon`.
+    struct Restore(Option<Capture>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let capture = self.0.take();
+            CAPTURE.with(|c| *c.borrow_mut() = capture);
+        }
+    }
+
+    let prev = CAPTURE.with(|c| c.borrow_mut().replace(Capture::Buffer(String::new())));
+    let _restore = Restore(prev);
+    let ret = f();
+    match CAPTURE.with(|c| c.borrow_mut().take()) {
+        Some(Capture::Buffer(output)) => (ret, output),
+        _ => (ret, String::new()),
+    }
+}
+
+// Returns `true` if the output is captured by `run` or `run_with`.
+pub(crate) fn captured() -> bool {
+    CAPTURE.with(|c| c.borrow().is_some())
+}
+
+fn input_override() -> Option<Box<dyn BufRead>> {
+    let input = INPUT.with(|i| i.borrow_mut().take())?;
+    Some(input)
+}
+
+/// Returns the reader for the input.
+///
+/// If the environment variable `ARGIO_INPUT` is set, the file at that path is opened. Otherwise, the standard input is used.
+pub fn reader() -> Box<dyn BufRead> {
+    if let Some(input) = input_override() {
+        // The connection of `connect` is kept track of in the session.
+        if in_session() {
+            Box::new(Tee(input))
+        } else {
+            input
+        }
+    } else if let Some(path) = env::var_os("ARGIO_INPUT") {
+        match File::open(&path) {
+            Ok(file) => tee(BufReader::new(file)),
+            Err(err) => panic!("failed to open `{}`: {}", path.to_string_lossy(), err),
+        }
+    } else {
+        tee(BufReader::new(io::stdin()))
+    }
+}
+
+/// Returns the reader for the input of `input_file_arg`.
+///
+/// If a command line argument is given, the file at that path is opened. Otherwise, the input is the same as [`reader`].
+pub fn arg_reader() -> Box<dyn BufRead> {
+    match args::positional().into_iter().next() {
+        Some(path) => open(Path::new(&path)),
+        None => reader(),
+    }
+}
+
+/// Returns the reader for the input given by `input_str`.
+pub fn str_reader(input: &'static str) -> Box<dyn BufRead> {
+    input_override().unwrap_or_else(|| tee(input.as_bytes()))
+}
+
+/// Opens the file at `path` for the input.
+pub fn open(path: &Path) -> Box<dyn BufRead> {
+    if let Some(input) = input_override() {
+        return input;
+    }
+    match File::open(path) {
+        Ok(file) => tee(BufReader::new(file)),
+        Err(err) => panic!("failed to open `{}`: {}", path.display(), err),
+    }
+}
+
+/// Returns the paths of the input and the output files for `#[argio(files)]`.
+///
+/// The input file is given by the first command line argument, the environment variable `ARGIO_INPUT`, or `input.txt`. The output file is given by the second command line argument, the environment variable `ARGIO_OUTPUT`, or the name of the input file with `input` replaced by `output` (`.out` is appended if it does not contain `input`).
+pub fn file_paths() -> (PathBuf, PathBuf) {
+    let mut args = args::positional().into_iter();
+    let input = args
+        .next()
+        .or_else(|| env::var_os("ARGIO_INPUT"))
+        .map_or_else(|| PathBuf::from("input.txt"), PathBuf::from);
+    let output = args
+        .next()
+        .or_else(|| env::var_os("ARGIO_OUTPUT"))
+        .map_or_else(|| output_path(&input), PathBuf::from);
+    (input, output)
+}
+
+fn output_path(input: &Path) -> PathBuf {
+    let name = input
+        .file_name()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match name.rfind("input") {
+        Some(pos) => input.with_file_name(format!("{}output{}", &name[..pos], &name[pos + 5..])),
+        None => {
+            let mut name = OsString::from(input.as_os_str());
+            name.push(".out");
+            PathBuf::from(name)
+        }
+    }
+}
+
+fn output() -> &'static Mutex<Box<dyn Write + Send>> {
+    static OUTPUT: OnceLock<Mutex<Box<dyn Write + Send>>> = OnceLock::new();
+    OUTPUT.get_or_init(|| Mutex::new(Box::new(io::stdout())))
+}
+
+static REDIRECT: OnceLock<File> = OnceLock::new();
+
+/// Redirects the output of the generated code from the standard output to the file at `path`.
+///
+/// The environment variable `ARGIO_OUTPUT` takes precedence over `path`. Nothing is done if neither is given, or while the output is captured. This has to be called before [`buffer`] or [`accumulate`], which write to the file instead.
+pub fn redirect(path: Option<&str>) {
+    if captured() {
+        return;
+    }
+    let path = match env::var_os("ARGIO_OUTPUT").or_else(|| path.map(OsString::from)) {
+        Some(path) => PathBuf::from(path),
+        None => return,
+    };
+    let file = match File::create(&path) {
+        Ok(file) => file,
+        Err(err) => panic!("failed to create `{}`: {}", path.display(), err),
+    };
+    let file = REDIRECT.get_or_init(|| file);
+    *output().lock().unwrap() = Box::new(file);
+}
+
+// Returns the standard output, or the file given by `redirect`.
+fn stdout() -> Box<dyn Write + Send> {
+    match REDIRECT.get() {
+        Some(file) => Box::new(file),
+        None => Box::new(io::stdout()),
+    }
+}
+
+/// Buffers the output of the generated code to the standard output.
+///
+/// The buffer has `capacity` bytes if given.
+pub fn buffer(capacity: Option<usize>) {
+    *output().lock().unwrap() = Box::new(writer(capacity, stdout()));
+}
+
+fn writer<W: Write>(capacity: Option<usize>, inner: W) -> BufWriter<W> {
+    match capacity {
+        Some(capacity) => BufWriter::with_capacity(capacity, inner),
+        None => BufWriter::new(inner),
+    }
+}
+
+/// Accumulates the whole output of the generated code in memory, and writes it to the standard output at once when flushed at the end.
+///
+/// The memory for `capacity` bytes is reserved if given.
+pub fn accumulate(capacity: Option<usize>) {
+    let buf = Vec::with_capacity(capacity.unwrap_or(0));
+    *output().lock().unwrap() = Box::new(Accumulate(buf));
+}
+
+struct Accumulate(Vec<u8>);
+
+impl Write for Accumulate {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut stdout = stdout();
+        stdout.write_all(&self.0)?;
+        self.0.clear();
+        stdout.flush()
+    }
+}
+
+/// Redirects the output of the generated code to the file at `path`.
+///
+/// The output is buffered with `capacity` bytes if given.
+pub fn create(path: &Path, capacity: Option<usize>) {
+    if CAPTURE.with(|c| c.borrow().is_some()) {
+        return;
+    }
+    let file = match File::create(path) {
+        Ok(file) => file,
+        Err(err) => panic!("failed to create `{}`: {}", path.display(), err),
+    };
+    *output().lock().unwrap() = Box::new(writer(capacity, file));
+}
+
+/// Connects the input and the output to the TCP socket at `addr` for interactive mode.
+///
+/// The environment variable `ARGIO_CONNECT` takes precedence over `addr`. Nothing is done if neither is given, or while the output is captured.
+pub fn connect(addr: Option<&str>) {
+    if captured() {
+        return;
+    }
+    let addr = match env::var("ARGIO_CONNECT")
+        .ok()
+        .or_else(|| addr.map(String::from))
+    {
+        Some(addr) => addr,
+        None => return,
+    };
+    let stream = match TcpStream::connect(&addr) {
+        Ok(stream) => stream,
+        Err(err) => panic!("failed to connect to `{}`: {}", addr, err),
+    };
+    // Queries are small and each of them waits for the answer, so they are sent right away.
+    let _ = stream.set_nodelay(true);
+    let reader = match stream.try_clone() {
+        Ok(reader) => reader,
+        Err(err) => panic!("failed to connect to `{}`: {}", addr, err),
+    };
+    INPUT.with(|i| *i.borrow_mut() = Some(Box::new(BufReader::new(reader))));
+    *output().lock().unwrap() = Box::new(BufWriter::new(stream));
+}
+
+/// Writes formatted text to the output.
+pub fn print(args: fmt::Arguments) {
+    if in_session() {
+        transcribe(true, &args.to_string());
+    }
+    if capture(|buf| fmt::Write::write_fmt(buf, args)) {
+        return;
+    }
+    if RECORDING.load(Ordering::Relaxed) {
+        record_output(&args.to_string());
+    }
+    if let Err(err) = output().lock().unwrap().write_fmt(args) {
+        panic!("failed to write the output: {}", err);
+    }
+}
+
+/// Writes formatted text and a newline to the output.
+pub fn println(args: fmt::Arguments) {
+    if in_session() {
+        transcribe(true, &format!("{}\n", args));
+    }
+    if capture(|buf| {
+        fmt::Write::write_fmt(buf, args).and_then(|_| fmt::Write::write_char(buf, '\n'))
+    }) {
+        return;
+    }
+    if RECORDING.load(Ordering::Relaxed) {
+        record_output(&format!("{}\n", args));
+    }
+    let mut output = output().lock().unwrap();
+    if let Err(err) = output.write_fmt(args).and_then(|_| output.write_all(b"\n")) {
+        panic!("failed to write the output: {}", err);
+    }
+}
+
+/// Writes `value` and a newline to the output.
+///
+/// This is used to print a return value of a primitive number type. With the feature `fast-fmt`, the number is formatted by `itoa` or `ryu` instead of `Display`. The output is the same as with `Display`, except that the last digit of a float may differ when two digits are equally close to it, and both are parsed to the same value.
+pub fn println_number(value: impl Number) {
+    #[cfg(feature = "fast-fmt")]
+    value.format(|s| {
+        if capture(|buf| {
+            buf.push_str(s);
+            buf.push('\n');
+            Ok(())
+        }) {
+            return;
+        }
+        if RECORDING.load(Ordering::Relaxed) {
+            record_output(s);
+            record_output("\n");
+        }
+        let mut output = output().lock().unwrap();
+        if let Err(err) = output
+            .write_all(s.as_bytes())
+            .and_then(|_| output.write_all(b"\n"))
+        {
+            panic!("failed to write the output: {}", err);
+        }
+    });
+    #[cfg(not(feature = "fast-fmt"))]
+    println(format_args!("{}", value));
+}
+
+/// A primitive number type which can be printed by [`println_number`].
+pub trait Number: fmt::Display {
+    /// Calls `f` with the number formatted as with `Display`.
+    fn format<R>(&self, f: impl FnOnce(&str) -> R) -> R;
+}
+
+macro_rules! impl_number_int {
+    ($($t:ty)*) => {$(
+        impl Number for $t {
+            #[cfg(feature = "fast-fmt")]
+            fn format<R>(&self, f: impl FnOnce(&str) -> R) -> R {
+                f(itoa::Buffer::new().format(*self))
+            }
+
+            #[cfg(not(feature = "fast-fmt"))]
+            fn format<R>(&self, f: impl FnOnce(&str) -> R) -> R {
+                f(&self.to_string())
+            }
+        }
+    )*};
+}
+
+impl_number_int!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+macro_rules! impl_number_float {
+    ($($t:ty)*) => {$(
+        impl Number for $t {
+            #[cfg(feature = "fast-fmt")]
+            fn format<R>(&self, f: impl FnOnce(&str) -> R) -> R {
+                if self.is_finite() {
+                    let mut buf = String::new();
+                    display_float(ryu::Buffer::new().format_finite(*self), &mut buf);
+                    f(&buf)
+                } else {
+                    f(&self.to_string())
+                }
+            }
+
+            #[cfg(not(feature = "fast-fmt"))]
+            fn format<R>(&self, f: impl FnOnce(&str) -> R) -> R {
+                f(&self.to_string())
+            }
+        }
+    )*};
+}
+
+impl_number_float!(f32 f64);
+
+// Rewrites a float formatted by `ryu`, such as `1.0`, `1.5e-7` or `1e16`, into the format of
+// `Display`, such as `1`, `0.00000015` or `10000000000000000`. Both give the shortest digits.
+#[cfg(feature = "fast-fmt")]
+fn display_float(s: &str, out: &mut String) {
+    let (sign, s) = match s.strip_prefix('-') {
+        Some(s) => ("-", s),
+        None => ("", s),
+    };
+    let (mantissa, exp) = match s.split_once('e') {
+        Some((mantissa, exp)) => (mantissa, exp.parse::<isize>().unwrap()),
+        None => (s, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let frac = if frac == "0" { "" } else { frac };
+    let digits = format!("{}{}", int, frac);
+    // The position of the decimal point in `digits`.
+    let point = int.len() as isize + exp;
+    out.push_str(sign);
+    if point <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', -point as usize));
+        out.push_str(&digits);
+    } else if point as usize >= digits.len() {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat_n('0', point as usize - digits.len()));
+    } else {
+        out.push_str(&digits[..point as usize]);
+        out.push('.');
+        out.push_str(&digits[point as usize..]);
+    }
+}
+
+// Writes to the captured output if any, and returns whether it was captured.
+fn capture(f: impl FnOnce(&mut String) -> fmt::Result) -> bool {
+    CAPTURE.with(|c| match c.borrow_mut().as_mut() {
+        Some(Capture::Buffer(buf)) => {
+            f(buf).unwrap();
+            true
+        }
+        Some(Capture::Channel(tx)) => {
+            let mut buf = String::new();
+            f(&mut buf).unwrap();
+            // The judge may have finished already.
+            let _ = tx.send(buf);
+            true
+        }
+        None => false,
+    })
+}
+
+/// Flushes the output.
+pub fn flush() {
+    if let Err(err) = output().lock().unwrap().flush() {
+        panic!("failed to flush the output: {}", err);
+    }
+}
+
+pub(crate) fn flush_on_panic() {
+    if let Ok(mut output) = output().try_lock() {
+        let _ = output.flush();
+    }
+}
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static RECORD: Mutex<Option<(Vec<u8>, Vec<u8>)>> = Mutex::new(None);
+
+/// Starts recording the input and the output for `#[argio(record)]`.
+///
+/// Nothing is recorded while the output is captured by [`run`].
+pub fn record() {
+    if CAPTURE.with(|c| c.borrow().is_some()) {
+        return;
+    }
+    *RECORD.lock().unwrap() = Some((vec![], vec![]));
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+/// Saves the recorded input and output into the directory `dir`.
+///
+/// They are saved as `<hash>.in` and `<hash>.out`, where `<hash>` is the hash of the input. An input already recorded is kept as is, so that its output can be compared after the program is changed.
+pub fn save_record(dir: &str) {
+    RECORDING.store(false, Ordering::Relaxed);
+    let (input, output) = match RECORD.lock().unwrap().take() {
+        Some(record) => record,
+        None => return,
+    };
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    let path = Path::new(dir).join(format!("{:016x}", hasher.finish()));
+    let input_path = path.with_extension("in");
+    if input_path.exists() {
+        return;
+    }
+    let ret = fs::create_dir_all(dir)
+        .and_then(|_| fs::write(path.with_extension("out"), &output))
+        .and_then(|_| fs::write(&input_path, &input));
+    if let Err(err) = ret {
+        diag::message(
+            Level::Warning,
+            format_args!("failed to record `{}`: {}", input_path.display(), err),
+        );
+    }
+}
+
+fn record_output(s: &str) {
+    if let Some((_, output)) = RECORD.lock().unwrap().as_mut() {
+        output.extend_from_slice(s.as_bytes());
+    }
+}
+
+// Wraps `reader` to record the input read from it if recording or in an interactive session.
+fn tee(reader: impl BufRead + 'static) -> Box<dyn BufRead> {
+    if RECORDING.load(Ordering::Relaxed) || in_session() {
+        Box::new(Tee(reader))
+    } else {
+        Box::new(reader)
+    }
+}
+
+struct Tee<R>(R);
+
+impl<R: BufRead> Read for Tee<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.0.read(buf)?;
+        record_input(&buf[..len]);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for Tee<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.0.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The buffer is filled already, so this does not read anything.
+        if let Ok(buf) = self.0.fill_buf() {
+            record_input(&buf[..amt.min(buf.len())]);
+        }
+        self.0.consume(amt);
+    }
+}
+
+fn record_input(buf: &[u8]) {
+    if RECORDING.load(Ordering::Relaxed) {
+        if let Some((input, _)) = RECORD.lock().unwrap().as_mut() {
+            input.extend_from_slice(buf);
+        }
+    }
+    if in_session() {
+        transcribe(false, &String::from_utf8_lossy(buf));
+    }
+}
+
+// The number of the lines of the interaction kept for `expect!`.
+const TRANSCRIPT_LINES: usize = 20;
+
+// The interaction of a local run in interactive mode.
+struct Session {
+    start: Instant,
+    transcript: VecDeque<(bool, String)>,
+    queries: usize,
+    sent: usize,
+    received: usize,
+}
+
+/// Starts keeping track of the interaction in interactive mode.
+///
+/// The last lines are shown when `expect!` fails, and [`report_session`] prints the numbers of the queries and the bytes. This must be called before the input is opened. Nothing is kept while the output is captured, as the judge shows the transcript by itself.
+pub fn start_session() {
+    if captured() {
+        return;
+    }
+    SESSION.with(|s| {
+        *s.borrow_mut() = Some(Session {
+            start: Instant::now(),
+            transcript: VecDeque::new(),
+            queries: 0,
+            sent: 0,
+            received: 0,
+        })
+    });
+}
+
+/// Prints the numbers of the queries (the lines printed) and the bytes exchanged since [`start_session`], and the time taken, to stderr.
+pub fn report_session() {
+    let session = match SESSION.with(|s| s.borrow_mut().take()) {
+        Some(session) => session,
+        None => return,
+    };
+    diag::message(
+        Level::Info,
+        format_args!(
+            "{} quer{}, sent {} byte(s), received {} byte(s) in {}ms",
+            session.queries,
+            if session.queries == 1 { "y" } else { "ies" },
+            session.sent,
+            session.received,
+            session.start.elapsed().as_millis()
+        ),
+    );
+}
+
+fn in_session() -> bool {
+    SESSION.with(|s| s.borrow().is_some())
+}
+
+fn transcribe(from_program: bool, text: &str) {
+    SESSION.with(|s| {
+        let mut s = s.borrow_mut();
+        let session = match s.as_mut() {
+            Some(session) => session,
+            None => return,
+        };
+        if from_program {
+            session.queries += text.matches('\n').count();
+            session.sent += text.len();
+        } else {
+            session.received += text.len();
+        }
+        let lines = &mut session.transcript;
+        for line in text.split_inclusive('\n') {
+            match lines.back_mut() {
+                Some((from, last)) if *from == from_program && !last.ends_with('\n') => {
+                    last.push_str(line)
+                }
+                _ => lines.push_back((from_program, line.to_string())),
+            }
+        }
+        while lines.len() > TRANSCRIPT_LINES {
+            lines.pop_front();
+        }
+    });
+}
+
+/// Panics because the response `response` of the judge does not match `expected`, with the last lines of the interaction.
+///
+/// This is used by `expect!` in interactive mode.
+#[track_caller]
+pub fn unexpected(response: &str, expected: &str) -> ! {
+    let transcript = SESSION.with(|s| {
+        let mut ret = String::new();
+        let s = s.borrow();
+        for (from_program, line) in s.iter().flat_map(|session| &session.transcript) {
+            let mark = if *from_program { '<' } else { '>' };
+            ret.push_str(&format!("\n{} {}", mark, line.trim_end_matches('\n')));
+        }
+        ret
+    });
+    if transcript.is_empty() {
+        panic!(
+            "unexpected response `{}`; expected `{}`",
+            response, expected
+        );
+    }
+    panic!(
+        "unexpected response `{}`; expected `{}`\n--- transcript (> input, < output){}",
+        response, expected, transcript
+    );
+}
+
+static STATS: AtomicBool = AtomicBool::new(false);
+static TOKENS: AtomicUsize = AtomicUsize::new(0);
+static WRITTEN: AtomicUsize = AtomicUsize::new(0);
+static FLUSHES: AtomicUsize = AtomicUsize::new(0);
+
+/// Starts counting the tokens read, the bytes written and the flushes for `#[argio(io_stats)]`.
+///
+/// This must be called after the output is set up by [`buffer`], [`accumulate`] or [`create`].
+pub fn start_stats() {
+    if CAPTURE.with(|c| c.borrow().is_some()) {
+        return;
+    }
+    let mut output = output().lock().unwrap();
+    let inner = std::mem::replace(&mut *output, Box::new(io::sink()));
+    *output = Box::new(Counting(inner));
+    STATS.store(true, Ordering::Relaxed);
+}
+
+/// Prints the numbers counted since [`start_stats`] to stderr.
+pub fn report_stats() {
+    if !STATS.load(Ordering::Relaxed) {
+        return;
+    }
+    diag::message(
+        Level::Info,
+        format_args!(
+            "read {} token(s), wrote {} byte(s), flushed {} time(s)",
+            TOKENS.load(Ordering::Relaxed),
+            WRITTEN.load(Ordering::Relaxed),
+            FLUSHES.load(Ordering::Relaxed)
+        ),
+    );
+}
+
+pub(crate) fn count_token() {
+    if STATS.load(Ordering::Relaxed) {
+        TOKENS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+struct Counting<W>(W);
+
+impl<W: Write> Write for Counting<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.0.write(buf)?;
+        WRITTEN.fetch_add(len, Ordering::Relaxed);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        FLUSHES.fetch_add(1, Ordering::Relaxed);
+        self.0.flush()
+    }
+}
+
+/// Mirrors the output of the generated code to stderr for `#[argio(tee)]`, with `prefix` at the start of each line.
+///
+/// The output is mirrored when it is written, before it is flushed. This must be called after the output is set up by [`buffer`], [`accumulate`] or [`create`].
+pub fn mirror(prefix: &'static str) {
+    if CAPTURE.with(|c| c.borrow().is_some()) {
+        return;
+    }
+    let mut output = output().lock().unwrap();
+    let inner = std::mem::replace(&mut *output, Box::new(io::sink()));
+    *output = Box::new(Mirror {
+        inner,
+        prefix,
+        line_start: true,
+    });
+}
+
+struct Mirror<W> {
+    inner: W,
+    prefix: &'static str,
+    line_start: bool,
+}
+
+impl<W: Write> Write for Mirror<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        let mut stderr = io::stderr().lock();
+        for line in buf[..len].split_inclusive(|&b| b == b'\n') {
+            if self.line_start {
+                stderr.write_all(self.prefix.as_bytes())?;
+            }
+            stderr.write_all(line)?;
+            self.line_start = line.ends_with(b"\n");
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Returns `true` if the case `case` (0-origin) should be solved.
+///
+/// If the environment variable `ARGIO_ONLY_CASE` is set to a case number (1-origin), only that case is solved. If the environment variable `ARGIO_CASES` is set to a range of case numbers (1-origin) such as `10..20`, `10..=20` or `10..`, only the cases in the range are solved.
+pub fn selected_case(case: usize) -> bool {
+    let (start, end) = selected_cases();
+    start <= case && case < end
+}
+
+/// Returns the first case (0-origin) to be solved, which is not preceded by the separator.
+pub fn first_selected_case() -> usize {
+    selected_cases().0
+}
+
+// Returns the half-open range of the cases selected by `ARGIO_ONLY_CASE` and `ARGIO_CASES`.
+fn selected_cases() -> (usize, usize) {
+    static SELECTED: OnceLock<(usize, usize)> = OnceLock::new();
+    *SELECTED.get_or_init(|| {
+        let mut ret = (0, usize::MAX);
+        if let Ok(value) = env::var("ARGIO_ONLY_CASE") {
+            let case = parse_case(&value)
+                .unwrap_or_else(|| panic!("invalid ARGIO_ONLY_CASE: `{}`", value));
+            ret = (case, case + 1);
+        }
+        if let Ok(value) = env::var("ARGIO_CASES") {
+            let (start, end) =
+                parse_cases(&value).unwrap_or_else(|| panic!("invalid ARGIO_CASES: `{}`", value));
+            ret = (ret.0.max(start), ret.1.min(end));
+        }
+        ret
+    })
+}
+
+// Parses a 1-origin case number into a 0-origin index.
+fn parse_case(s: &str) -> Option<usize> {
+    s.trim().parse::<usize>().ok()?.checked_sub(1)
+}
+
+// Parses a range of 1-origin case numbers into a half-open range of 0-origin indices.
+fn parse_cases(s: &str) -> Option<(usize, usize)> {
+    let s = s.trim();
+    let (start, end) = s.split_once("..")?;
+    let start = if start.is_empty() {
+        0
+    } else {
+        parse_case(start)?
+    };
+    let end = if let Some(end) = end.strip_prefix('=') {
+        parse_case(end)? + 1
+    } else if end.is_empty() {
+        usize::MAX
+    } else {
+        parse_case(end)?
+    };
+    Some((start, end))
+}
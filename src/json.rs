@@ -0,0 +1,46 @@
+//! JSON input and output of `#[argio(format = json)]`.
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::io::Read;
+
+/// Reads a JSON object from `reader`.
+pub fn read(reader: impl Read) -> Map<String, Value> {
+    match serde_json::from_reader(reader) {
+        Ok(Value::Object(map)) => map,
+        Ok(value) => panic!("the input is not a JSON object: {}", value),
+        Err(err) => panic!("failed to parse the input as JSON: {}", err),
+    }
+}
+
+/// Takes the field `name` of `doc` as the value of the parameter `name`.
+///
+/// A missing field is read as `null`, so that it can be read as `None` of an `Option`.
+pub fn param<T: DeserializeOwned>(doc: &mut Map<String, Value>, name: &str) -> T {
+    let value = doc.remove(name).unwrap_or(Value::Null);
+    match serde_json::from_value(value) {
+        Ok(value) => value,
+        Err(err) => panic!(
+            "failed to read parameter `{}` from the JSON input: {}",
+            name, err
+        ),
+    }
+}
+
+/// Panics if the length of the parameter `name` is not `expected`.
+pub fn check_len(name: &str, actual: usize, expected: usize) {
+    if actual != expected {
+        panic!(
+            "parameter `{}` has {} element(s) in the JSON input, but {} expected",
+            name, actual, expected
+        );
+    }
+}
+
+/// Serializes the return value into JSON.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> String {
+    match serde_json::to_string(value) {
+        Ok(s) => s,
+        Err(err) => panic!("failed to write the output as JSON: {}", err),
+    }
+}
@@ -0,0 +1,182 @@
+//! A local judge for interactive programs.
+
+use std::{
+    fmt::{Display, Write as _},
+    io::{self, Read},
+    str::FromStr,
+    sync::mpsc::{Receiver, RecvTimeoutError, Sender},
+    time::Duration,
+};
+
+// How long the judge waits for the output of the program before giving up. Both of them
+// waiting for each other is a common bug of interactive programs.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The judge side of an interaction with the program, given to the function set by
+/// `#[argio(judge = ...)]`.
+///
+/// The judge writes lines to the input of the program with [`send`](Judge::send), and reads its
+/// output with [`read_line`](Judge::read_line) or [`read`](Judge::read). All of them are
+/// recorded, and shown as the transcript when the test fails.
+pub struct Judge {
+    input: Option<Sender<Vec<u8>>>,
+    output: Receiver<String>,
+    pending: String,
+    transcript: Vec<(bool, String)>,
+}
+
+impl Judge {
+    pub(crate) fn new(input: Sender<Vec<u8>>, output: Receiver<String>) -> Self {
+        Judge {
+            input: Some(input),
+            output,
+            pending: String::new(),
+            transcript: vec![],
+        }
+    }
+
+    /// Writes `line` and a newline to the input of the program.
+    pub fn send(&mut self, line: impl Display) {
+        let line = format!("{}\n", line);
+        self.record(false, &line);
+        if let Some(input) = &self.input {
+            // The program may have finished already, which is reported after the judge.
+            let _ = input.send(line.into_bytes());
+        }
+    }
+
+    /// Reads a line of the output of the program, without the newline.
+    ///
+    /// Returns `None` if the program has finished. Panics if no output comes for 10 seconds.
+    pub fn read_line(&mut self) -> Option<String> {
+        loop {
+            if let Some(pos) = self.pending.find('\n') {
+                let line = self.pending[..pos].trim_end_matches('\r').to_string();
+                self.pending.drain(..=pos);
+                return Some(line);
+            }
+            if !self.receive() {
+                if self.pending.is_empty() {
+                    return None;
+                }
+                return Some(std::mem::take(&mut self.pending));
+            }
+        }
+    }
+
+    /// Reads a whitespace separated token of the output of the program and parses it.
+    ///
+    /// Panics if the program has finished, or if the token cannot be parsed.
+    pub fn read<T: FromStr>(&mut self) -> T
+    where
+        T::Err: Display,
+    {
+        loop {
+            let start = self.pending.len() - self.pending.trim_start().len();
+            let rest = &self.pending[start..];
+            if let Some(end) = rest.find(char::is_whitespace) {
+                let token = rest[..end].to_string();
+                self.pending.drain(..start + end);
+                return token.parse().unwrap_or_else(|err| {
+                    panic!("failed to parse the output `{}`: {}", token, err)
+                });
+            }
+            if !self.receive() {
+                let token = self.pending.trim().to_string();
+                if token.is_empty() {
+                    panic!("the program finished without the expected output");
+                }
+                self.pending.clear();
+                return token.parse().unwrap_or_else(|err| {
+                    panic!("failed to parse the output `{}`: {}", token, err)
+                });
+            }
+        }
+    }
+
+    // Waits for the next output of the program, and returns `false` if it has finished.
+    fn receive(&mut self) -> bool {
+        match self.output.recv_timeout(TIMEOUT) {
+            Ok(output) => {
+                self.record(true, &output);
+                self.pending.push_str(&output);
+                true
+            }
+            Err(RecvTimeoutError::Disconnected) => false,
+            Err(RecvTimeoutError::Timeout) => panic!(
+                "no output from the program for {} seconds; it may be waiting for the input",
+                TIMEOUT.as_secs()
+            ),
+        }
+    }
+
+    fn record(&mut self, from_program: bool, text: &str) {
+        match self.transcript.last_mut() {
+            Some((from, last)) if *from == from_program && !last.ends_with('\n') => {
+                last.push_str(text)
+            }
+            _ => self.transcript.push((from_program, text.to_string())),
+        }
+    }
+
+    // Closes the input of the program, so that it reads the end of the input.
+    pub(crate) fn close(&mut self) {
+        self.input = None;
+    }
+
+    // Records the rest of the output of the program after it has finished.
+    pub(crate) fn drain(&mut self) {
+        while let Ok(output) = self.output.try_recv() {
+            self.record(true, &output);
+        }
+    }
+
+    // Returns the interaction so far, where `>` is the input and `<` is the output of the
+    // program.
+    pub(crate) fn transcript(&self) -> String {
+        let mut ret = String::new();
+        for (from_program, text) in &self.transcript {
+            let mark = if *from_program { '<' } else { '>' };
+            for line in text.lines() {
+                writeln!(ret, "{} {}", mark, line).unwrap();
+            }
+        }
+        ret
+    }
+}
+
+/// A reader of the input sent by [`Judge::send`].
+pub(crate) struct ChannelReader {
+    input: Receiver<Vec<u8>>,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl ChannelReader {
+    pub(crate) fn new(input: Receiver<Vec<u8>>) -> Self {
+        ChannelReader {
+            input,
+            buf: vec![],
+            pos: 0,
+        }
+    }
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.buf.len() {
+            match self.input.recv() {
+                Ok(input) => {
+                    self.buf = input;
+                    self.pos = 0;
+                }
+                // The judge has finished.
+                Err(_) => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.buf.len() - self.pos);
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
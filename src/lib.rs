@@ -31,6 +31,221 @@
 //!
 //! from the standard input and outputs the sum to the standard output.
 //!
+//! Parameters can be declared with `mut`, so that the body can sort or modify the inputs in place without rebinding them. This also works with tuple patterns, global parameters and all the modes of `multicase`. Parameters that cannot be read from the input, such as references, `impl Trait` and struct patterns, and generic functions are rejected with an error pointing at them.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio]
+//! fn main(n: usize, mut a: [i64; n]) -> i64 {
+//!     a.sort();
+//!     a[n / 2]
+//! }
+//! ```
+//!
+//! Attributes and doc comments on the function, including inner attributes such as `#![allow(...)]` in its body, are kept on the generated function.
+//!
+//! With `entry`, the function is kept as an ordinary function taking the parsed values, and a separate `main` is generated to read the input, call it and print the result. This lets unit tests and benchmarks call the function directly. The types of the parameters are converted to the types of the parsed values (e.g. `[i64; n]` to `Vec<i64>` and `Usize1` to `usize`).
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(entry)]
+//! fn solve(n: usize, a: [i64; n]) -> i64 {
+//!     a.into_iter().sum()
+//! }
+//!
+//! assert_eq!(solve(3, vec![1, 2, 3]), 6);
+//! # main();
+//! ```
+//!
+//! With `runner`, a function `__argio_run(input: &str) -> String` is also generated, which runs the program with the given string as its input and returns its output. This lets tests check the whole program against the samples. The name of the function can be changed with `runner = name`.
+//!
+//! ```
+//! # use argio::argio;
+//! #[argio(multicase, runner = run)]
+//! fn solve(a: i64, b: i64) -> i64 {
+//!     a + b
+//! }
+//!
+//! assert_eq!(run("2\n1 2\n3 4\n"), "Case #1: 3\nCase #2: 7\n");
+//! ```
+//!
+//! With the feature `wasm`, `wasm = name` generates such a function named `name` exported by `wasm-bindgen` when the target is `wasm32`, instead of touching the standard input and output. This lets browser-based visualizers and judge simulators run the same solution. The crate has to be built as a `cdylib`, and `timing`, `bench` and other features measuring time cannot be used on `wasm32-unknown-unknown`.
+//!
+//! ```ignore
+//! #[argio(wasm = solve)]
+//! pub fn run(n: usize, a: [i64; n]) -> i64 {
+//!     a.into_iter().sum()
+//! }
+//! ```
+//!
+//! ```text
+//! // In JavaScript, with the bindings generated by wasm-bindgen
+//! solve("3\n1 2 3\n") // "6\n"
+//! ```
+//!
+//! The samples of the problem can be checked by `#[argio::test]`, which generates a test running the program with `input` and comparing its output with `expected`. Trailing whitespace of each line and trailing empty lines are ignored in the comparison. It can be put above or below `#[argio]`, and any number of them can be given.
+//!
+//! ```ignore
+//! #[argio(multicase)]
+//! #[argio::test(input = "2\n1 2\n3 4\n", expected = "Case #1: 3\nCase #2: 7\n")]
+//! #[argio::test(input = "1\n-1 1\n", expected = "Case #1: 0\n")]
+//! fn main(a: i64, b: i64) -> i64 {
+//!     a + b
+//! }
+//! ```
+//!
+//! Samples stored in files can be checked by `tests`, which takes a path pattern relative to the package root. A test is generated for each input matching the pattern, and its output is compared with the file of the same name with the extension `.out`. `*` and `?` can be used in the file name. Inputs are searched at compile time, so adding a new sample requires the program to be rebuilt. The output files are snapshots described below, so they can be written by the program itself.
+//!
+//! ```ignore
+//! // tests/samples/1.in, tests/samples/1.out, tests/samples/2.in, ...
+//! #[argio(tests = "tests/samples/*.in")]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.into_iter().sum()
+//! }
+//! ```
+//!
+//! With `oj`, the samples downloaded by `oj download` of [online-judge-tools](https://github.com/online-judge-tools/oj) are checked in the same way. It is a shorthand for `tests = "test/*.in"`, and `oj = "dir"` reads them from another directory. `float_tolerance` and `checker` below also apply to these tests.
+//!
+//! ```ignore
+//! // test/sample-1.in, test/sample-1.out, test/sample-2.in, ...
+//! #[argio(oj)]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.into_iter().sum()
+//! }
+//! ```
+//!
+//! With `compete`, the samples are read from the test suite written by [cargo-compete](https://github.com/qryxip/cargo-compete), `testcases/{alias}.yml`, where the alias of the binary is taken from `[package.metadata.cargo-compete.bin]` of `Cargo.toml`. `compete = "path"` reads another file. A test is generated for each case, `match: Float` sets `float_tolerance` unless it is given, and the tests fail if a run exceeds `timelimit` in release builds (`cargo test --release`). Only `Batch` problems and the YAML written by cargo-compete are supported.
+//!
+//! ```ignore
+//! // src/bin/a.rs, tested with testcases/a.yml
+//! #[argio(compete)]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.into_iter().sum()
+//! }
+//! ```
+//!
+//! Code blocks labeled `input` and `output` in the doc comment of the function are also checked as samples. They are paired in the order of appearance.
+//!
+//! ```ignore
+//! /// ```input
+//! /// 3
+//! /// 1 2 3
+//! /// ```
+//! ///
+//! /// ```output
+//! /// 6
+//! /// ```
+//! #[argio]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.into_iter().sum()
+//! }
+//! ```
+//!
+//! If the answer is a real number, set `float_tolerance` to compare the output token by token. A number is accepted if either its absolute or relative error from the expected one is within the tolerance.
+//!
+//! ```ignore
+//! #[argio(float_tolerance = 1e-6)]
+//! #[argio::test(input = "1\n", expected = "3.14159265\n")]
+//! fn main(r: f64) -> f64 {
+//!     r * r * std::f64::consts::PI
+//! }
+//! ```
+//!
+//! If the problem has multiple correct answers, set `checker` to a function which takes the input, the expected output and the actual output and returns whether the actual output is accepted.
+//!
+//! ```ignore
+//! #[argio(checker = check)]
+//! #[argio::test(input = "4 5\n1 4 2 3\n", expected = "1 2\n")]
+//! fn main(n: usize, k: i64, a: [i64; n]) -> String {
+//!     ...
+//! }
+//!
+//! fn check(input: &str, expected: &str, actual: &str) -> bool {
+//!     ...
+//! }
+//! ```
+//!
+//! `#[argio::stress]` generates a test comparing the program with a brute force solution on random inputs. `gen` takes an [`random::Rng`] seeded by the iteration number and returns an input, and `brute` is another function with `#[argio]`. The test runs `iters` (default: 100) inputs, and reports the first failing input after shrinking it by running `gen` again with smaller random values. `checker` and `float_tolerance` are also used for the comparison.
+//!
+//! ```ignore
+//! use argio::{argio, random::Rng};
+//!
+//! #[argio]
+//! #[argio::stress(gen = gen, brute = brute, iters = 1000)]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     ...
+//! }
+//!
+//! #[argio]
+//! fn brute(n: usize, a: [i64; n]) -> i64 {
+//!     ...
+//! }
+//!
+//! #[argio::generator]
+//! fn gen(rng: &mut Rng) -> String {
+//!     let n = rng.range(1..=8);
+//!     let a = (0..n).map(|_| rng.range(-10..=10).to_string()).collect::<Vec<_>>();
+//!     format!("{}\n{}\n", n, a.join(" "))
+//! }
+//! ```
+//!
+//! `#[argio::generator]` on `main` taking an [`random::Rng`] makes a program printing a random input, which is seeded by the first command line argument, the environment variable `ARGIO_SEED`, or the current time. On other functions, it only allows them to be unused outside of tests, so that they can be given to `#[argio::stress]`. Like `#[argio]`, it takes `crate = path` when `argio` is re-exported or bundled under another path.
+//!
+//! ```ignore
+//! use argio::random::Rng;
+//!
+//! #[argio::generator]
+//! fn main(rng: &mut Rng) -> String {
+//!     let n = rng.range(1..=8);
+//!     let a = (0..n).map(|_| rng.range(-10..=10).to_string()).collect::<Vec<_>>();
+//!     format!("{}\n{}", n, a.join(" "))
+//! }
+//! ```
+//!
+//! `#[argio::property]` checks a property of the output on random inputs, which are made from the parameters without writing a generator. `check` takes the input and the output and returns whether the property holds. Integers are taken from `range` of the parameter attrs (default: `0..=10` or `-10..=10`), and the lengths of strings and `[T]` from `len` (default: `1..=10` and `0..=10`). Multicase is not supported.
+//!
+//! ```ignore
+//! #[argio(output = Wrap)]
+//! #[argio::property(check = sorted, iters = 1000)]
+//! fn main(#[argio(range = 0..=20)] n: usize, a: [i64; n]) -> Vec<i64> {
+//!     ...
+//! }
+//!
+//! fn sorted(input: &str, output: &str) -> bool {
+//!     ...
+//! }
+//! ```
+//!
+//! With `snapshot`, the expected outputs can be kept in files in the given directory instead. `#[argio::test]` without `expected` is compared with `<test name>.out`, and `#[argio::stress]` without `brute` is compared with `<test name>.out` holding the outputs for all the seeds. When the tests are run with the environment variable `ARGIO_UPDATE=1`, the files are written with the current outputs instead of being compared, and otherwise the difference is shown on a mismatch. The outputs of `tests` are also written in this way.
+//!
+//! ```ignore
+//! #[argio(snapshot = "tests/snapshots")]
+//! #[argio::test(input = "3\n1 2 3\n")]
+//! #[argio::stress(gen = gen, iters = 20)]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.into_iter().map(|x| x * x).sum()
+//! }
+//! ```
+//!
+//! With `memory_limit`, the generated tests fail if the peak of the heap memory allocated by a run exceeds the limit, given in the same format as `stack_size`. The memory is counted by a global allocator installed in the tests, so `memory_limit` can be used only once in a crate, and it cannot be used with another global allocator. Memory allocated by other threads, as with `stack_size` or `parallel`, is not counted.
+//!
+//! ```ignore
+//! #[argio(memory_limit = "256MB")]
+//! #[argio::test(input = "1000\n", expected = "499500\n")]
+//! fn main(n: u64) -> u64 {
+//!     (0..n).sum()
+//! }
+//! ```
+//!
+//! With `record`, the inputs and the outputs of local runs are recorded into the given directory, and a test named `<fn name>_recorded` checks that the program still produces the recorded outputs for them. This is useful to make sure that a refactoring does not change the behavior. The recording is done only in debug builds, and each input is saved as `<hash>.in` with its output `<hash>.out`. An input recorded already is not overwritten, so remove the files to record the new outputs. The outputs are compared in the same way as samples, so `checker` and `float_tolerance` are also used.
+//!
+//! ```ignore
+//! #[argio(record = "tests/records")]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.into_iter().sum()
+//! }
+//! ```
+//!
 //! You can change the macro for the input by setting the `input` parameter. A macro takes the arguments of the function as they are.
 //!
 //! ```compile_fail
@@ -45,6 +260,268 @@
 //! }
 //! ```
 //!
+//! `argio` also has a small built-in input backend, [`scan!`], which has no dependencies and supports the same syntax except for `proconio`'s derive macros. You can select it with `input = argio::scan`. If you disable the default `proconio` feature, it becomes the default backend and `proconio` is not compiled at all.
+//!
+//! ```toml
+//! [dependencies]
+//! argio = { version = "0.2", default-features = false }
+//! ```
+//!
+//! The markers for the built-in backend are in the [`marker`] module.
+//!
+//! A point on the plane given as `x y` can be read with [`marker::Point`], which works with both `proconio` and the built-in backend. The value read is a `Point<T>` with the fields `x` and `y` and the basic operations for geometry, such as addition, subtraction, scalar multiplication, `dot` and `cross`.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! use argio::marker::Point;
+//!
+//! #[argio]
+//! fn main(n: usize, p: [Point<i64>; n]) -> i64 {
+//!     (0..n).map(|i| p[i].cross(p[(i + 1) % n])).sum::<i64>().abs()
+//! }
+//! ```
+//!
+//! An interval given as `l r` can be read with [`marker::Interval`] as a half-open range `Range<T>`, which can index slices directly. `#[argio]` recognizes `Interval` by name. The input is taken as a closed interval of 0-indexed values by default. The parameter attr `half_open` takes it as `[l, r)` instead, and `one_based` converts 1-indexed values to 0-indexed, so the off-by-one conversions are written only once in the signature.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio]
+//! fn main(n: usize, a: [u64; n], #[argio(one_based)] range: Interval) -> u64 {
+//!     a[range].iter().sum()
+//! }
+//! ```
+//!
+//! Other backends are available in the [`backend`] module behind cargo features of the same names: `text_io` and `whiteread`. They share the grammar of `scan!` and only delegate reading tokens to the corresponding crate.
+//!
+//! ```ignore
+//! #[argio(input = argio::backend::whiteread::input)]
+//! fn main(n: usize, x: [i64; n]) -> i64 {
+//!     x.into_iter().sum()
+//! }
+//! ```
+//!
+//! If `fast_input` is specified, the whole standard input is read into a buffer before the function runs and tokens are taken from it. This is faster for huge inputs but cannot be used for interactive problems. In this mode, the input macro is called with a leading `from source,` argument like `proconio`'s `input!`, so a custom input macro has to accept it.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(fast_input)]
+//! fn main(n: usize, x: [u64; n]) -> u64 {
+//!     x.into_iter().fold(0, |acc, x| acc ^ x)
+//! }
+//! ```
+//!
+//! `byte_input` goes further for inputs of millions of tokens. The whole input is read into a byte buffer, split at ASCII whitespace, and each number is parsed from its slice of the buffer, without validating the whole input as UTF-8 or copying tokens into strings. It cannot be used with `fast_input` or `interactive`.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(byte_input)]
+//! fn main(n: usize, x: [u64; n]) -> u64 {
+//!     x.into_iter().max().unwrap_or(0)
+//! }
+//! ```
+//!
+//! With `input_file_arg`, when the program is run with a command line argument, the input is read from the file at that path instead of the standard input. This is handy for local testing. Without it, the command line arguments are left to the program, such as the options of `#[argio(arg)]`. It cannot be used with `input_str`, `files` or `interactive`.
+//!
+//! ```text
+//! $ cargo run -- testcases/1.in
+//! ```
+//!
+//! The environment variable `ARGIO_INPUT` also redirects the input to a file without any code changes, which works even when a shell redirection is not available. With `input_file_arg`, a command line argument takes precedence over it.
+//!
+//! ```text
+//! $ ARGIO_INPUT=testcases/1.in cargo run
+//! ```
+//!
+//! These apply to the default input macro. A custom input macro set by `input` reads from wherever it reads.
+//!
+//! With `files`, both the input and the output are files, as in the download-input/upload-output workflow of Meta Hacker Cup. The input file is given by the first command line argument, `ARGIO_INPUT` or `input.txt`, and the output file is given by the second command line argument, `ARGIO_OUTPUT`, or the name of the input file with `input` replaced by `output`. `print!` and `println!` in the function body are also redirected to the output file.
+//!
+//! ```no_run
+//! # use argio::argio;
+//! #[argio(files, multicase)]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.iter().sum()
+//! }
+//! ```
+//!
+//! ```text
+//! $ cargo run -- hc_round1_a_input.txt
+//! argio: reading `hc_round1_a_input.txt`, writing `hc_round1_a_output.txt`
+//! ```
+//!
+//! Without `files`, the output can still be written to a file with `output_file`, or with the environment variable `ARGIO_OUTPUT`, which takes precedence over it. Only the output goes to the file, and stderr is left for logs. This keeps the answers of runs in parallel apart without a shell redirection.
+//!
+//! ```no_run
+//! # use argio::argio;
+//! #[argio(multicase, input_file_arg, output_file = "answer.txt")]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.iter().sum()
+//! }
+//! ```
+//!
+//! ```text
+//! $ ARGIO_OUTPUT=out/seed-1.txt cargo run -- in/seed-1.txt
+//! ```
+//!
+//! With `tee`, everything written to the output is also written to stderr as it is produced, so that it can be watched while the output goes to a file. `tee = "prefix"` puts the prefix at the start of each mirrored line.
+//!
+//! ```text
+//! $ ARGIO_OUTPUT=out.txt cargo run < in.txt  # with #[argio(multicase, tee = "> ")]
+//! > Case #1: 3
+//! > Case #2: 7
+//! ```
+//!
+//! If `input_str` is specified, the input is read from the given string instead of the standard input. This is useful for examples, doctests and quick experiments.
+//!
+//! ```
+//! # use argio::argio;
+//! #[argio(input_str = "3\n1 2 3\n")]
+//! fn main(n: usize, x: [i64; n]) -> i64 {
+//!     x.into_iter().sum()
+//! }
+//! ```
+//!
+//! If `normalize_input` is specified, a leading byte order mark and all `\r` characters are removed from the input before tokenizing, so that test files saved on Windows can be read as they are.
+//!
+//! If `strict_input` is specified, it is checked that the whole input has been read after the function (or all the cases) finished, and the program panics with the unread tokens otherwise. This catches a signature that does not match the actual input format.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(strict_input, input_str = "3\n1 2 3 4\n")]
+//! fn main(n: usize, x: [i64; n]) -> i64 {
+//!     x.into_iter().sum()
+//! }
+//! ```
+//!
+//! A parameter of the type `Unzip<[(A, B); n]>` reads `n` records of `(A, B)` like `[(A, B); n]`, but gives them as a tuple of vectors `(Vec<A>, Vec<B>)`, one for each field. Tuples of up to four fields are supported, and `Unzip<[(A, B)]>` reads the number of the records first.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio]
+//! fn main(n: usize, (w, v): Unzip<[(usize, u64); n]>) -> u64 {
+//!     (0..n).filter(|&i| w[i] <= 10).map(|i| v[i]).sum()
+//! }
+//! ```
+//!
+//! For inputs divided into sections by blank lines, a parameter of a slice type `[T]` with `#[argio(until_blank)]` reads values of `T` until a blank line or the end of the input, instead of reading the length first. The blank lines are skipped, so the next parameter starts at the next section. This works with the built-in sources of `argio`, which keep track of lines, so a custom input macro is called with a leading `from source,` argument as with `fast_input`.
+//!
+//! ```no_run
+//! # use argio::argio;
+//! #[argio]
+//! fn main(#[argio(until_blank)] rules: [(u32, u32)], #[argio(until_blank)] queries: [u32]) -> usize {
+//!     queries.iter().filter(|&&q| rules.iter().all(|&(a, _)| a != q)).count()
+//! }
+//! ```
+//!
+//! A line with spaces, such as a name or a sentence, can be read as `String` with the marker `Line`, which `#[argio]` recognizes by name. The markers are recognized when they are written as they are or under `marker`, such as `argio::marker::Line`, so a type of the same name in another module, such as `geometry::Line`, is read as it is. If the line of the last token has more tokens, the rest of it is read. The parameters with `Line` are read with the built-in backend from the sources of `argio`, which keep track of lines, even if another backend is selected, and `[Line]` with `until_blank` reads lines until a blank line.
+//!
+//! ```no_run
+//! # use argio::argio;
+//! #[argio]
+//! fn main(n: usize, names: [Line; n]) -> usize {
+//!     names.iter().filter(|name| name.contains(' ')).count()
+//! }
+//! ```
+//!
+//! The body can read more from the same input at any point with `read!(T)`, which reads a value of `T` with the input macro, and `readline!()`, which reads a line as `Option<String>` in the same way as `Line`, returning `None` at the end of the input. They are escape hatches for ad-hoc formats that do not fit into the parameters, and are not available if a custom input macro reads the standard input by itself.
+//!
+//! ```no_run
+//! # use argio::argio;
+//! #[argio]
+//! fn main(q: usize) -> u64 {
+//!     let mut sum = 0;
+//!     for _ in 0..q {
+//!         if read!(String) == "add" {
+//!             sum += read!(u64);
+//!         } else {
+//!             println!("{}", readline!().unwrap());
+//!         }
+//!     }
+//!     sum
+//! }
+//! ```
+//!
+//! If a parameter has a type that is not supported by the input macro but implements `FromStr`, mark it with `#[argio(from_str)]`. The token is read as a string and parsed with `FromStr`. Arrays of such a type are also supported.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! # use std::str::FromStr;
+//! struct Time {
+//!     h: u32,
+//!     m: u32,
+//! }
+//!
+//! impl FromStr for Time {
+//!     // ...
+//! #     type Err = ();
+//! #     fn from_str(s: &str) -> Result<Self, ()> {
+//! #         let (h, m) = s.split_once(':').ok_or(())?;
+//! #         Ok(Time { h: h.parse().map_err(|_| ())?, m: m.parse().map_err(|_| ())? })
+//! #     }
+//! }
+//!
+//! #[argio]
+//! fn main(n: usize, #[argio(from_str)] ts: [Time; n]) -> u32 {
+//!     ts.iter().map(|t| t.h * 60 + t.m).sum()
+//! }
+//! ```
+//!
+//! Constraints of the problem can be written as `range` and `len` attributes of parameters. `range` checks the value (or every element of an array) and `len` checks the length. They are checked only in debug builds, and a violation panics with the name and the value of the parameter.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio]
+//! fn main(
+//!     #[argio(range = 1..=100_000)] n: usize,
+//!     #[argio(range = -1_000_000_000..=1_000_000_000)] a: [i64; n],
+//! ) -> i64 {
+//!     a.into_iter().max().unwrap()
+//! }
+//! ```
+//!
+//! Parameters marked with `#[argio(arg)]` are not read from the input but taken from the command line options, which is handy for the parameters of heuristic solvers. The option of `max_iters` is `--max-iters value` or `--max-iters=value`. `default = expr` gives the value when the option is not given; without it the option is required, unless the type is `Option<T>`. Unknown options are an error, and with `input_file_arg`, the other command line argument is the input file.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(input_file_arg)]
+//! fn main(
+//!     #[argio(arg, default = 0)] seed: u64,
+//!     #[argio(arg)] max_iters: Option<usize>,
+//!     n: usize,
+//!     a: [i64; n],
+//! ) -> i64 {
+//!     a.into_iter().sum::<i64>() + seed as i64 + max_iters.unwrap_or(0) as i64
+//! }
+//! ```
+//!
+//! ```text
+//! $ cargo run -- --seed 42 --max-iters=1000 input.txt
+//! ```
+//!
+//! Likewise, `#[argio(env = "NAME")]` takes the parameter from the environment variable `NAME`, with `default` and `Option<T>` working in the same way. With both `arg` and `env`, the option is used if given, and the environment variable otherwise.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio]
+//! fn main(#[argio(env = "TIME_LIMIT_MS", default = 2000)] time_limit_ms: u64, n: usize) -> u64 {
+//!     time_limit_ms * n as u64
+//! }
+//! ```
+//!
+//! ```text
+//! $ TIME_LIMIT_MS=500 cargo run < input.txt
+//! ```
+//!
+//! When reading the input fails, the panic message is followed by a note telling which parameter was being read, the case number and the approximate position in the input:
+//!
+//! ```text
+//! thread 'main' panicked at ...:
+//! failed to parse the input `x` to the value of type `i64`: ...
+//! argio: while reading `a` of case #2 (around line 4, column 3)
+//! ```
+//!
+//! When stderr is a terminal, the messages of argio such as this note and the reports of `timing` are colored, with the names and the values in backquotes in bold. They are plain text when stderr is redirected, or when the environment variable `NO_COLOR` is set.
+//!
 //! Because the `Display` trait is used to display the return value, functions such as `Vec` which does not implement the `Display` trait cannot be compiled as it is.
 //!
 //! You can customize the behavior of the output by using a wrapper struct that implements the `Display` trait.
@@ -77,6 +554,22 @@
 //! 0 2 4 6 8 10 12 14 16 18
 //! ```
 //!
+//! With the feature `fast-fmt`, a return value of a primitive integer or float type is formatted by `itoa` or `ryu` and written to the output directly instead of going through `Display`, which is faster for programs printing many numbers, such as a multicase program with a million cases. The output is the same except that the last digit of a float may differ in rare cases where two digits are equally close to it, and both are parsed to the same value. Values with `output` are still printed with `Display`.
+//!
+//! With the feature `json`, `format = json` reads the parameters from the fields of the same names in a JSON object, and writes the return value as JSON, so that the same function can be used as a command line tool or a component of other programs. The types of the parameters and the return value have to implement `Deserialize` and `Serialize` of `serde`, except that `[T; n]` and `[T]` are read as `Vec<T>`, and the length of `[T; n]` is checked. A missing field is read as `null`, so an `Option` parameter can be omitted. Markers such as `Usize1` cannot be used, and it cannot be used with `multicase` or the attrs for the textual input and output.
+//!
+//! ```ignore
+//! #[argio(format = json)]
+//! fn main(n: usize, a: [i64; n], name: Option<String>) -> (String, Vec<i64>) {
+//!     (name.unwrap_or_default(), a.into_iter().rev().collect())
+//! }
+//! ```
+//!
+//! ```text
+//! $ echo '{"n": 3, "a": [1, 2, 3], "name": "rev"}' | cargo run
+//! ["rev",[3,2,1]]
+//! ```
+//!
 //! Of course, you can also output manually. If the return value of the function is `()`, it does not output anything to the standard output, so you can output it manually and return `()`.
 //!
 //! ```should_panic
@@ -117,9 +610,21 @@
 //! }
 //! ```
 //!
+//! Arguments can be passed to the wrapper after the return value, as in `output = Wrap(arg, ...)`, which prints `Wrap(ret, arg, ...)`. The [`output`] module has wrappers for common formats. [`output::Join`] prints the elements separated by the given separator, such as `","`, `"\t"` or `"\n"`. [`output::IfEmpty`] prints the elements separated by spaces, or the given value such as `-1` if there are none. [`output::Fraction`] prints a pair `(p, q)` of integers as a fraction reduced to lowest terms, separated by the given separator such as `"/"` or `" "`. [`output::Columns`], used as `output = Columns`, prints a tuple of vectors of the same length as rows, one element from each vector in a line. [`output::Chunks`] prints the elements with the given number of them in each line. [`output::AddOne`] prints 0-indexed answers as 1-indexed ones, with one added to every integer in integers, tuples and vectors of them, as the counterpart of `Usize1` of the input. [`output::Bits`] prints a `Vec<bool>` as a string of `0` and `1`, and [`output::BitMask`] prints the given number of the lowest bits of an integer in the same way, from the lowest bit. [`output::Edges`] prints the number of edges followed by the edges, one in a line, adding the given offset such as `1` to the vertices.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! use argio::output::Join;
+//!
+//! #[argio(output = Join(","))]
+//! fn main(n: usize) -> Vec<usize> {
+//!     (0..n).map(|i| i * 2).collect()
+//! }
+//! ```
+//!
 //! If `multicase` is specified as an attribute, it can be used to automatically execute multiple inputs for multiple cases that start with the number of cases.
 //!
-//! The value of the attribute `multicase` is a string to be displayed at the top of each case. The variable `i` contains the case number of 0 origin, so you can customize the display by using it.
+//! The value of the attribute `multicase` is a string to be displayed at the top of each case. The variable `i` contains the case number of 0 origin and `case` contains that of 1 origin, so you can customize the display by using them. The format string can contain any number of `{expr}` or `{expr:spec}` placeholders, and the number of cases is available as `cases` unless `eof` is used (e.g. `"Case {case}/{cases}: "`). Use `{{` and `}}` for literal braces. The header is printed after the input of the case is read, so the parameters of the function can also be used in it (e.g. `"n={n}: "`). The names used by the generated code do not conflict with the parameters and the local variables of the function, though `i`, `case` and `cases` in the format string always refer to the ones described above.
 //!
 //! ```should_panic
 //! # use argio::argio;
@@ -148,6 +653,534 @@
 //! Case #2: 0 2 4
 //! Case #3: 0 2 4 6 8
 //! ```
+//!
+//! More options of multicase can be given in the form of `multicase(...)`. The format string is given by `format`. If `eof` is specified, the cases are repeated until the input is exhausted instead of reading the number of cases first.
+//!
+//! ```no_run
+//! # use argio::argio;
+//! #[argio(multicase(eof, format = ""))]
+//! fn main(a: i64, b: i64) -> i64 {
+//!     a + b
+//! }
+//! ```
+//!
+//! ```text
+//! $ echo "1 2 3 4 5 6" | cargo run
+//! 3
+//! 7
+//! 11
+//! ```
+//!
+//! The number of cases can also be given by an expression with `count`, instead of the first token of the input.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase(count = 3))]
+//! fn main(a: i64, b: i64) -> i64 {
+//!     a + b
+//! }
+//! ```
+//!
+//! When the first line of the input contains other values after the number of cases, the whole first line can be declared by `first_line`. Its first value is the number of cases, which must be `usize`, and all the values are available in the function body.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase(first_line = "t: usize, modulo: u64"))]
+//! fn main(a: u64, b: u64) -> u64 {
+//!     a * b % modulo
+//! }
+//! ```
+//!
+//! The number of cases is available in the function body under the name given by `cases_var`.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase(cases_var = t))]
+//! fn main(n: usize) -> usize {
+//!     // The total budget is shared by all the cases
+//!     n * 100 / t
+//! }
+//! ```
+//!
+//! Similarly, the 0-origin index of the current case is available under the name given by `case_index`. The name can also be used in the format string in place of `i`. In this case, the 1-origin case number is also available in the function body as `case`, unless a parameter has the same name.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase(case_index = idx, format = "Case #{idx+1}: "))]
+//! fn main(n: u64) -> u64 {
+//!     n.wrapping_mul(idx as u64 + 1)
+//! }
+//! ```
+//!
+//! With `header = manual`, the header is not printed automatically. Instead, it is printed when the function body calls `case_header!()`, which is useful when the body prints its own multi-line output.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase(format = "Case #{case}:\n", header = manual))]
+//! fn main(n: usize) {
+//!     case_header!();
+//!     for i in 0..n {
+//!         println!("{}", i);
+//!     }
+//! }
+//! ```
+//!
+//! Instead of `format`, a format of the whole line of the case can be given by `case_format`, in which the return value is available as `ret`. The line is printed at once after the case finishes.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase(case_format = "Case #{i+1}: {ret}"))]
+//! fn main(a: i64, b: i64) -> i64 {
+//!     a + b
+//! }
+//! ```
+//!
+//! A string given by `case_separator` is printed between the outputs of the cases, but not after the last one.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase(case_separator = "\n"))]
+//! fn main(n: usize) -> usize {
+//!     n * 2
+//! }
+//! ```
+//!
+//! ```text
+//! $ echo "2 1 2" | cargo run
+//! Case #1: 2
+//!
+//! Case #2: 4
+//! ```
+//!
+//! With `read_all`, the inputs of all the cases are read before solving any of them. If a name is given as `read_all = name`, the inputs of all the cases are available in the function body as a slice of tuples of the parameters, which is useful for offline algorithms across the cases. In that case, the parameters have to implement `Clone`. `cases_var` can also be used with `eof` in this mode.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase(read_all = all))]
+//! fn main(n: usize, a: [i64; n]) -> usize {
+//!     // The number of cases that have fewer elements than this case
+//!     all.iter().filter(|(m, _)| *m < n).count()
+//! }
+//! ```
+//!
+//! With `parallel`, which requires the `rayon` feature, the cases are read first and then solved in parallel on the thread pool of `rayon`. The outputs of the cases, including those printed by `print!` and `println!` in the function body, are buffered and printed in the order of the cases.
+//!
+//! ```ignore
+//! # use argio::argio;
+//! #[argio(multicase(parallel))]
+//! fn main(n: u64) -> u64 {
+//!     (1..=n).sum()
+//! }
+//! ```
+//!
+//! With `case_timeout` and `fallback`, each case is solved on a worker thread, and if it does not finish within the given time (e.g. `"500ms"`, `"5s"` or `"2m"`), the case is abandoned and the value of `fallback` is used as its answer. The abandoned thread keeps running in the background until the program exits. Under `#[argio::test]` and `runner`, the output of the body is captured when the case finishes, and nothing is captured from an abandoned case. In this mode, the parameters and the return value have to be `Send + 'static`, and the global parameters have to implement `Clone`. It cannot be used with `parallel` or `read_all = name`.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase(case_timeout = "5s", fallback = -1))]
+//! fn main(n: i64) -> i64 {
+//!     (1..=n).sum()
+//! }
+//! ```
+//!
+//! For inputs that consist of multiple test sets, each of which contains its own cases, `sets` adds an outer level of cases. It takes `format`, `eof`, `count`, `cases_var`, `case_index` and `case_separator` in the same way as `multicase`, and `i` in its format is the index of the set.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(sets(format = "Set #{i+1}:\n", case_index = s), multicase = "  Case #{i+1}: ")]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.iter().sum::<i64>() * (s as i64 + 1)
+//! }
+//! ```
+//!
+//! ```text
+//! $ echo "2  2 1 5 2 1 2  1 3 1 2 3" | cargo run
+//! Set #1:
+//!   Case #1: 5
+//!   Case #2: 3
+//! Set #2:
+//!   Case #1: 12
+//! ```
+//!
+//! If the environment variable `ARGIO_ONLY_CASE` is set to a case number (1-origin), the inputs of all the cases are still read, but only that case is solved and printed. This helps to reproduce a failure in a large input file.
+//!
+//! ```text
+//! $ ARGIO_ONLY_CASE=57 cargo run < large.in
+//! Case #57: 42
+//! ```
+//!
+//! Similarly, `ARGIO_CASES` selects a range of cases such as `10..20`, `10..=20` or `10..`, which is useful for splitting a huge input across machines.
+//!
+//! ```text
+//! $ ARGIO_CASES=1..=50 cargo run < large.in > 1.out
+//! $ ARGIO_CASES=51.. cargo run < large.in > 2.out
+//! ```
+//!
+//! Parameters marked with `#[argio(global)]` are read only once, before the multicase loop, and are shared by all the cases. Global parameters must come before the others.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase)]
+//! fn main(#[argio(global)] modulo: u64, a: u64, b: u64) -> u64 {
+//!     (a * b) % modulo
+//! }
+//! ```
+//!
+//! ```text
+//! $ echo "1000 2  123 456  999 999" | cargo run
+//! Case #1: 88
+//! Case #2: 1
+//! ```
+//!
+//! With `progress`, the number of finished cases is shown on stderr while running, if stderr is a terminal.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase, progress)]
+//! fn main(n: u64) -> u64 {
+//!     (1..=n).sum()
+//! }
+//! ```
+//!
+//! With `timing`, the elapsed time of each case is printed to stderr, followed by the total and the maximum time of all the cases.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase, timing)]
+//! fn main(n: u64) -> u64 {
+//!     (1..=n).sum()
+//! }
+//! ```
+//!
+//! ```text
+//! $ echo "2 10 100000000" | cargo run
+//! argio: case #1 took 1.200µs
+//! Case #1: 55
+//! argio: case #2 took 1.024s
+//! Case #2: 5000000050000000
+//! argio: 2 case(s) took 1.024s in total, max 1.024s (case #2)
+//! ```
+//!
+//! With `bench`, the elapsed time of the whole run, from reading the input to flushing the output, is printed to stderr at the end, together with the peak memory usage of the process where it is available (currently on Linux). This is a quick check of the performance before submitting.
+//!
+//! ```text
+//! $ echo 10000000 | cargo run --release
+//! 10000000
+//! argio: took 141.520ms, peak memory 78.5 MiB
+//! ```
+//!
+//! With `io_stats`, the numbers of the tokens read, the bytes written and the flushes of the output are printed to stderr at the end. Together with `bench`, this helps to tell whether a slow program is bound by the computation or by the input and output.
+//!
+//! ```text
+//! $ echo 100 | cargo run
+//! 100
+//! argio: read 1 token(s), wrote 4 byte(s), flushed 1 time(s)
+//! ```
+//!
+//! By default, the output is written to the standard output as it is, so that it is printed in order with `print!` and `println!` in other functions. With `flush = at_end`, the output, including `print!` and `println!` in the function body, is buffered and flushed at the end of the program (or when it panics), which is much faster for a large output. Note that `print!` and `println!` in other functions are not buffered then, so they may be printed out of order. With `flush = per_case`, the output is also flushed after each case. With `flush = once`, the whole output is kept in memory and written to the standard output in one write at the end, so that it is not interleaved with the logs written to the standard error. `flush = never` is the default. With `output_capacity`, the buffer for the output is allocated with the given number of bytes in advance, which avoids reallocating it for a huge output, and the output is buffered as with `flush = at_end` unless `flush` is given.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase, flush = per_case)]
+//! fn main(n: u64) -> u64 {
+//!     n * 2
+//! }
+//! ```
+//!
+//! The body can flush the output at any point with `flush!()`, which flushes whatever the output of the generated code is written to, such as the buffer of the standard output or the output file. This is useful to show the progress of a long-running program or to stream the output.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio]
+//! fn main(n: usize, a: [u64; n]) {
+//!     for x in a {
+//!         println!("{}", x * x);
+//!         flush!();
+//!     }
+//! }
+//! ```
+//!
+//! For interactive problems, set `interactive`. The output is flushed on each `print!` and `println!` in the body, so a forgotten flush cannot hang the interaction, and the body can read more input with `input!`, which takes the same syntax as the parameters. It cannot be used with `fast_input`, `input_str`, `files` or `flush`. `print!` and `println!` in other functions are not rebound and write to the standard output directly, so queries should be printed from the body, or from a closure or a macro defined in it.
+//!
+//! `expect!(pattern)` reads the next token of the response of the judge and returns it as `String`, panicking if it does not match the pattern, such as `expect!("OK")` or `expect!("Yes" | "No")`. The panic message shows the last lines of the interaction, so a bug in the protocol is found at the point where it happens rather than as a wrong answer later.
+//!
+//! ```ignore
+//! #[argio(interactive)]
+//! fn main(n: usize) {
+//!     let sum: i64 = (1..=n).map(|i| {
+//!         println!("? {}", i);
+//!         read!(i64)
+//!     }).sum();
+//!     println!("! {}", sum);
+//!     expect!("OK");
+//! }
+//! ```
+//!
+//! At the end of an interactive run in a debug build, the number of the queries, which are the lines printed, the bytes sent and received, and the time taken are printed to stderr, so that the limits of the protocol can be checked before submitting.
+//!
+//! ```text
+//! argio: 12 queries, sent 96 byte(s), received 40 byte(s) in 35ms
+//! ```
+//!
+//! With `deadline = "1900ms"`, the body can ask the time left until the deadline, counted from the start of the program, with `remaining!()`, which returns a `Duration` and is zero after the deadline. This helps adaptive strategies to stop querying in time. With `on_deadline = f` in addition, `f()` is printed as the final answer and the program exits at the deadline if it is still running, instead of being killed by the judge. `f` takes no arguments, so the best answer so far has to be kept in a static. The program is not stopped in the tests with `judge`.
+//!
+//! ```ignore
+//! static BEST: AtomicU64 = AtomicU64::new(0);
+//!
+//! #[argio(interactive, deadline = "1900ms", on_deadline = give_up)]
+//! fn main(n: usize) {
+//!     for i in 1..=n {
+//!         if remaining!() < Duration::from_millis(100) {
+//!             break;
+//!         }
+//!         println!("? {}", i);
+//!         BEST.fetch_max(read!(u64), Ordering::Relaxed);
+//!     }
+//!     println!("{}", give_up());
+//! }
+//!
+//! fn give_up() -> String {
+//!     format!("! {}", BEST.load(Ordering::Relaxed))
+//! }
+//! ```
+//!
+//! With `judge`, a test is generated which runs the program against a judge function over in-memory channels. The judge takes a [`judge::Judge`] to send lines to the program and read its output, and returns whether the program is accepted. When it fails, the transcript of the interaction is shown.
+//!
+//! ```ignore
+//! use argio::judge::Judge;
+//!
+//! #[argio(interactive, judge = judge)]
+//! fn main(n: u64) {
+//!     let (mut lo, mut hi) = (1, n);
+//!     loop {
+//!         let mid = (lo + hi) / 2;
+//!         println!("? {}", mid);
+//!         input! {
+//!             res: String,
+//!         }
+//!         match res.as_str() {
+//!             "<" => hi = mid - 1,
+//!             ">" => lo = mid + 1,
+//!             _ => break,
+//!         }
+//!     }
+//! }
+//!
+//! fn judge(judge: &mut Judge) -> bool {
+//!     judge.send(100);
+//!     ...
+//! }
+//! ```
+//!
+//! The program can also talk to a judge over a TCP connection instead of the standard input and output. It connects to the address in the environment variable `ARGIO_CONNECT`, or the one given by `connect`, if any.
+//!
+//! ```ignore
+//! #[argio(interactive, connect = "localhost:8000")]
+//! fn main(n: u64) {
+//!     // ...
+//! }
+//! ```
+//!
+//! ```text
+//! $ ARGIO_CONNECT=judge.local:9000 cargo run
+//! ```
+//!
+//! `async fn` is also supported with `executor`, which selects the executor to run the body to completion: `pollster` or `futures`. Each of them requires the cargo feature of the same name.
+//!
+//! ```ignore
+//! # use argio::argio;
+//! #[argio(executor = pollster)]
+//! async fn main(n: usize, a: [i64; n]) -> i64 {
+//!     let mut sum = 0;
+//!     for x in a {
+//!         sum += fetch(x).await;
+//!     }
+//!     sum
+//! }
+//! ```
+//!
+//! With `runtime = tokio`, which requires the `tokio` feature, the body runs on a multi-threaded Tokio runtime instead, so that async libraries built on Tokio can be used in it. The runtime is created once and shared by all the cases. The input is still read synchronously before the body runs, so any input macro works as it is, and `print!` and `println!` in the body write to the same output as usual.
+//!
+//! ```ignore
+//! # use argio::argio;
+//! #[argio(runtime = tokio)]
+//! async fn main(n: u64) -> u64 {
+//!     tokio::task::spawn_blocking(move || (1..=n).sum()).await.unwrap()
+//! }
+//! ```
+//!
+//! With `stack_size`, the program runs on a thread with a stack of the given size (e.g. `"256MB"`, `"1GB"`), which avoids stack overflows of deep recursions such as DFS on a long path. The thread is joined before the program exits, and a panic in it is propagated.
+//!
+//! ```
+//! # use argio::argio;
+//! #[argio(stack_size = "256MB", input_str = "100000\n")]
+//! fn main(n: u64) -> u64 {
+//!     fn sum(n: u64) -> u64 {
+//!         if n == 0 { 0 } else { n + sum(n - 1) }
+//!     }
+//!     sum(n)
+//! }
+//! ```
+//!
+//! With `dump`, the code generated by the macro is pretty-printed and written to the given file during the compilation, so that you can review exactly what will run. A relative path is resolved from the root of the crate.
+//!
+//! ```no_run
+//! # use argio::argio;
+//! #[argio(multicase, dump = "target/argio_expanded.rs")]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.iter().sum()
+//! }
+//! ```
+//!
+//! The generated code refers to the runtime of `argio` only through a single `use ::argio as __argio;` at the top of each generated function, and the other paths are fully qualified from it. When `argio` is inlined into the submission by a bundler such as `cargo-equip`, or re-exported from another crate, the path can be changed with `crate = path`.
+//!
+//! ```ignore
+//! #[argio(multicase, crate = crate::__cargo_equip::crates::argio)]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.iter().sum()
+//! }
+//! ```
+//!
+//! With `bundle`, a single self-contained file for the submission is written during the compilation, which consists of the source file with the function replaced by the generated code, and the parts of the runtime of `argio` used by it as `mod argio`. It does not refer to any other crates, so it can be submitted to judges that do not have `argio` or `proconio`. The input is read by the built-in backend in the bundle, so the markers should be taken from [`marker`] and `input = argio::scan` is recommended to check the same behavior locally. It cannot be used with `parallel`, `executor` or `runtime`, which need other crates.
+//!
+//! ```no_run
+//! # use argio::argio;
+//! #[argio(multicase, input = argio::scan, bundle = "target/submission.rs")]
+//! fn main(n: usize, a: [i64; n]) -> i64 {
+//!     a.iter().sum()
+//! }
+//! ```
+//!
+//! Instead of `#[proconio::fastout]`, `fastout` can be given to `argio`, which is the same as `flush = at_end`. If `#[proconio::fastout]` is put below `#[argio]`, it is removed from the generated function, and if it is put above `#[argio]`, the original body is taken back from its expansion. In both cases, the output is buffered as with `fastout` unless `flush` is given, so code written for `proconio` can be used as it is.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase, fastout)]
+//! fn main(n: u64) -> u64 {
+//!     n * 2
+//! }
+//! ```
+//!
+//! If the function returns `Result<(), E>`, such as `anyhow::Result<()>` or `Result<(), Box<dyn Error>>`, it is not printed as an answer. Instead, the generated function returns the same type, and an error is returned from it as soon as a case fails, after flushing the output so far. Together with `?` in the body, this is convenient for command line tools. It cannot be used with `parallel`.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! # use std::error::Error;
+//! #[argio(multicase)]
+//! fn main(s: String) -> Result<(), Box<dyn Error>> {
+//!     let n: u64 = s.parse()?;
+//!     println!("{}", n * n);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! With `try`, `?` can be used in the body while the return type stays the type of the answer. The body runs in a closure returning `Result<T, Box<dyn Error>>`, and the program panics with the error if it fails. `return` in the body works as usual. It cannot be used on `async fn`.
+//!
+//! ```should_panic
+//! # use argio::argio;
+//! #[argio(multicase, try)]
+//! fn main(s: String) -> u64 {
+//!     let n: u64 = s.parse()?;
+//!     n * n
+//! }
+//! ```
+
+#[doc(hidden)]
+pub mod args;
+pub mod backend;
+#[doc(hidden)]
+pub mod check;
+#[doc(hidden)]
+pub mod diag;
+pub mod io;
+#[cfg(feature = "json")]
+#[doc(hidden)]
+pub mod json;
+pub mod judge;
+pub mod marker;
+#[doc(hidden)]
+pub mod memory;
+pub mod output;
+#[doc(hidden)]
+pub mod progress;
+pub mod random;
+#[cfg(feature = "tokio")]
+#[doc(hidden)]
+pub mod runtime;
+pub mod scan;
+#[doc(hidden)]
+pub mod testing;
+#[doc(hidden)]
+pub mod timing;
 
-pub use argio_macro::argio;
+pub use argio_macro::{argio, generator, property, stress, test};
+#[cfg(feature = "futures")]
+#[doc(hidden)]
+pub use futures;
+#[cfg(feature = "pollster")]
+#[doc(hidden)]
+pub use pollster;
+#[cfg(feature = "proconio")]
 pub use proconio;
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+pub use rayon;
+#[cfg(feature = "tokio")]
+#[doc(hidden)]
+pub use tokio;
+#[cfg(feature = "wasm")]
+#[doc(hidden)]
+pub use wasm_bindgen;
+
+#[cfg(feature = "proconio")]
+#[doc(hidden)]
+pub use proconio::input as __input;
+#[cfg(not(feature = "proconio"))]
+#[doc(hidden)]
+pub use scan as __input;
+
+#[doc(hidden)]
+pub fn parse_token<T>(name: &str, token: &str) -> T
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Debug,
+{
+    match token.parse() {
+        Ok(v) => v,
+        Err(err) => panic!(
+            "failed to parse `{}` as `{}` for parameter `{}`: {:?}",
+            token,
+            std::any::type_name::<T>(),
+            name,
+            err
+        ),
+    }
+}
+
+/// Converts records into a tuple of vectors for the parameters of the type `Unzip<[T; n]>`.
+#[doc(hidden)]
+pub trait Unzip: Sized {
+    type Output;
+
+    fn unzip(records: Vec<Self>) -> Self::Output;
+}
+
+macro_rules! impl_unzip {
+    ($($name:ident $i:tt)*) => {
+        impl<$($name),*> Unzip for ($($name,)*) {
+            type Output = ($(Vec<$name>,)*);
+
+            fn unzip(records: Vec<Self>) -> Self::Output {
+                let mut ret = ($(Vec::<$name>::with_capacity(records.len()),)*);
+                for record in records {
+                    $(ret.$i.push(record.$i);)*
+                }
+                ret
+            }
+        }
+    };
+}
+
+impl_unzip!(A 0 B 1);
+impl_unzip!(A 0 B 1 C 2);
+impl_unzip!(A 0 B 1 C 2 D 3);
@@ -0,0 +1,261 @@
+//! Marker types for the built-in input backend.
+//!
+//! These correspond to the markers in `proconio::marker`.
+
+use crate::scan::{Scan, Source};
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Range, Sub, SubAssign};
+
+/// Reads a token as `Vec<char>`.
+pub enum Chars {}
+
+impl Scan for Chars {
+    type Output = Vec<char>;
+
+    fn scan<S: Source + ?Sized>(source: &mut S) -> Vec<char> {
+        source.next_token_unwrap().chars().collect()
+    }
+}
+
+/// Reads a token as `Vec<u8>`.
+pub enum Bytes {}
+
+impl Scan for Bytes {
+    type Output = Vec<u8>;
+
+    fn scan<S: Source + ?Sized>(source: &mut S) -> Vec<u8> {
+        source.next_token_unwrap().bytes().collect()
+    }
+}
+
+/// Reads a whole line as `String`, including the spaces in it.
+///
+/// If the line of the last token has more tokens, the rest of it is read. Only the sources with the information of lines, such as [`Scanner`](crate::scan::Scanner), can read lines. `#[argio]` recognizes `Line` in the types of parameters by name, and reads those parameters with the built-in backend even if another one is selected.
+pub enum Line {}
+
+impl Scan for Line {
+    type Output = String;
+
+    fn scan<S: Source + ?Sized>(source: &mut S) -> String {
+        source
+            .next_line()
+            .expect("failed to get the next line; maybe reader reached an end of input")
+            .to_string()
+    }
+}
+
+/// Reads a 1-indexed `usize` and converts it to 0-indexed.
+pub enum Usize1 {}
+
+impl Scan for Usize1 {
+    type Output = usize;
+
+    fn scan<S: Source + ?Sized>(source: &mut S) -> usize {
+        usize::scan(source)
+            .checked_sub(1)
+            .expect("attempted to read the value 0 as a Usize1")
+    }
+}
+
+/// Reads a 1-indexed `isize` and converts it to 0-indexed.
+pub enum Isize1 {}
+
+impl Scan for Isize1 {
+    type Output = isize;
+
+    fn scan<S: Source + ?Sized>(source: &mut S) -> isize {
+        isize::scan(source)
+            .checked_sub(1)
+            .expect("attempted to read the value isize::MIN as an Isize1")
+    }
+}
+
+/// Reads two tokens `l r` as an interval and converts it to a half-open range.
+///
+/// The input is taken as the closed interval `[l, r]` of 0-indexed values by default, and the value read is `l..r + 1`. `#[argio(half_open)]` on the parameter takes it as `[l, r)` instead, and `#[argio(one_based)]` shifts 1-indexed values to 0-indexed. `#[argio]` recognizes `Interval` in the types of parameters by name and replaces it with one of the aliases below by the attributes, so it does not have to be imported.
+pub struct Interval<T = usize, const CLOSED: bool = true, const ONE_BASED: bool = false>(
+    PhantomData<T>,
+);
+
+/// Reads a closed interval of 1-indexed values.
+pub type Interval1<T = usize> = Interval<T, true, true>;
+
+/// Reads a half-open interval of 0-indexed values.
+pub type HalfOpen<T = usize> = Interval<T, false, false>;
+
+/// Reads a half-open interval of 1-indexed values.
+pub type HalfOpen1<T = usize> = Interval<T, false, true>;
+
+impl<T, const CLOSED: bool, const ONE_BASED: bool> Interval<T, CLOSED, ONE_BASED>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    fn range(l: T, r: T) -> Range<T> {
+        let one = T::from(1);
+        if ONE_BASED {
+            assert!(
+                l >= one && r >= one,
+                "attempted to read the value 0 as a 1-indexed Interval"
+            );
+        }
+        let start = if ONE_BASED { l - one } else { l };
+        let end = match (CLOSED, ONE_BASED) {
+            (true, true) => r,
+            (true, false) => r + one,
+            (false, true) => r - one,
+            (false, false) => r,
+        };
+        assert!(
+            start <= end,
+            "attempted to read a reversed interval as an Interval"
+        );
+        start..end
+    }
+}
+
+impl<T, const CLOSED: bool, const ONE_BASED: bool> Scan for Interval<T, CLOSED, ONE_BASED>
+where
+    T: Scan<Output = T> + Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    type Output = Range<T>;
+
+    fn scan<S: Source + ?Sized>(source: &mut S) -> Range<T> {
+        let l = T::scan(source);
+        let r = T::scan(source);
+        Self::range(l, r)
+    }
+}
+
+#[cfg(feature = "proconio")]
+impl<T, const CLOSED: bool, const ONE_BASED: bool> proconio::source::Readable
+    for Interval<T, CLOSED, ONE_BASED>
+where
+    T: proconio::source::Readable<Output = T>
+        + Copy
+        + PartialOrd
+        + Add<Output = T>
+        + Sub<Output = T>
+        + From<u8>,
+{
+    type Output = Range<T>;
+
+    fn read<R: std::io::BufRead, S: proconio::source::Source<R>>(source: &mut S) -> Range<T> {
+        let l = T::read(source);
+        let r = T::read(source);
+        Self::range(l, r)
+    }
+}
+
+/// Reads two tokens `x y` as a point.
+///
+/// `Point<T>` is also the type of the value read, with the basic operations for geometry: addition, subtraction and negation of points, multiplication and division by a scalar, [`dot`](Point::dot), [`cross`](Point::cross) and [`norm2`](Point::norm2). It is displayed as `x y`, so it can be returned as is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Point<T> {
+    /// The dot product of two points as vectors.
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The cross product of two points as vectors, which is positive if `other` is counterclockwise from `self`.
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// The squared length of the point as a vector.
+    pub fn norm2(self) -> T {
+        self.dot(self)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, k: T) -> Self {
+        Point::new(self.x * k, self.y * k)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Point<T> {
+    type Output = Self;
+
+    fn div(self, k: T) -> Self {
+        Point::new(self.x / k, self.y / k)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.x, self.y)
+    }
+}
+
+impl<T: Scan<Output = T>> Scan for Point<T> {
+    type Output = Self;
+
+    fn scan<S: Source + ?Sized>(source: &mut S) -> Self {
+        let x = T::scan(source);
+        let y = T::scan(source);
+        Point::new(x, y)
+    }
+}
+
+#[cfg(feature = "proconio")]
+impl<T: proconio::source::Readable<Output = T>> proconio::source::Readable for Point<T> {
+    type Output = Self;
+
+    fn read<R: std::io::BufRead, S: proconio::source::Source<R>>(source: &mut S) -> Self {
+        let x = T::read(source);
+        let y = T::read(source);
+        Point::new(x, y)
+    }
+}
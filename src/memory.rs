@@ -0,0 +1,95 @@
+//! Measurement of the memory usage for the tests generated with `#[argio(memory_limit)]`.
+//!
+//! The heap memory allocated by each thread is counted by a global allocator, since the tests
+//! run in parallel in the same process.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+thread_local! {
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// The global allocator counting the memory allocated by each thread.
+pub struct Counter;
+
+fn add(size: isize) {
+    // The thread locals may be unavailable while a thread is being destroyed.
+    let _ = CURRENT.try_with(|current| {
+        let value = current.get() + size;
+        current.set(value);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(value)));
+    });
+}
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            add(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            add(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        add(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            add(new_size as isize - layout.size() as isize);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f`, and panics if the peak of the heap memory allocated by it exceeds `limit` bytes.
+///
+/// Memory allocated by other threads, such as the one made by `stack_size`, and the stack are
+/// not counted.
+#[track_caller]
+pub fn check<T>(limit: usize, f: impl FnOnce() -> T) -> T {
+    let start = CURRENT.with(Cell::get);
+    PEAK.with(|peak| peak.set(start));
+    let ret = f();
+    let used = (PEAK.with(Cell::get) - start).max(0) as usize;
+    if !INSTALLED.load(Ordering::Relaxed) {
+        panic!("argio::memory::Counter is not the global allocator");
+    }
+    if used > limit {
+        panic!(
+            "memory usage {} exceeds the limit {}",
+            format_size(used),
+            format_size(limit)
+        );
+    }
+    ret
+}
+
+pub(crate) fn format_size(size: usize) -> String {
+    if size >= 1 << 20 {
+        format!("{:.1} MiB", size as f64 / (1 << 20) as f64)
+    } else if size >= 1 << 10 {
+        format!("{:.1} KiB", size as f64 / (1 << 10) as f64)
+    } else {
+        format!("{} B", size)
+    }
+}
@@ -0,0 +1,313 @@
+//! Wrappers of the return value to format the output.
+//!
+//! A wrapper is given by `output` of `#[argio]`, such as `output = Join(",")`, where the return value is passed as the first field and the arguments as the rest. It can also be returned from the function as it is.
+
+use std::fmt::{self, Display};
+
+/// Prints the elements separated by the separator.
+///
+/// `Join(vec![1, 2, 3], ",")` is printed as `1,2,3`.
+pub struct Join<T, S = &'static str>(pub T, pub S);
+
+impl<T, S: Display> Display for Join<T, S>
+where
+    for<'a> &'a T: IntoIterator,
+    for<'a> <&'a T as IntoIterator>::Item: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        join(f, &self.0, &self.1)
+    }
+}
+
+/// Prints the elements separated by spaces, or the fallback if there are none.
+///
+/// `IfEmpty(vec![], -1)` is printed as `-1`, and `IfEmpty(vec![1, 2], -1)` as `1 2`.
+pub struct IfEmpty<T, S = &'static str>(pub T, pub S);
+
+impl<T, S: Display> Display for IfEmpty<T, S>
+where
+    for<'a> &'a T: IntoIterator,
+    for<'a> <&'a T as IntoIterator>::Item: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if (&self.0).into_iter().next().is_none() {
+            self.1.fmt(f)
+        } else {
+            join(f, &self.0, ' ')
+        }
+    }
+}
+
+/// Prints a fraction `(p, q)` reduced to lowest terms, with the separator between `p` and `q`.
+///
+/// The denominator is made positive. `Fraction((6, -4), "/")` is printed as `-3/2`, and `Fraction((0, 5), " ")` as `0 1`.
+pub struct Fraction<T, S = &'static str>(pub T, pub S);
+
+macro_rules! impl_fraction {
+    ($($t:ty)*) => {$(
+        impl<S: Display> Display for Fraction<($t, $t), S> {
+            #[allow(unused_comparisons)]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let (mut p, mut q) = self.0;
+                assert!(q != 0, "the denominator of a fraction is zero");
+                if q < 0 {
+                    p = 0 - p;
+                    q = 0 - q;
+                }
+                let (mut a, mut b) = (q, p % q);
+                while b != 0 {
+                    let r = a % b;
+                    a = b;
+                    b = r;
+                }
+                let g = if a < 0 { 0 - a } else { a };
+                write!(f, "{}{}{}", p / g, self.1, q / g)
+            }
+        }
+    )*};
+}
+
+impl_fraction!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+/// Prints a tuple of vectors of the same length as rows, each of which has the `i`-th elements of them separated by spaces.
+///
+/// `Columns((vec![1, 2], vec!['a', 'b']))` is printed as `1 a` and `2 b` in two lines.
+pub struct Columns<T>(pub T);
+
+macro_rules! impl_columns {
+    ($($name:ident)*) => {
+        impl<$($name: Display),*> Display for Columns<($(Vec<$name>,)*)> {
+            #[allow(non_snake_case)]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let ($($name,)*) = &self.0;
+                let lens = [$($name.len()),*];
+                assert!(
+                    lens.iter().all(|&len| len == lens[0]),
+                    "the columns have different lengths: {:?}",
+                    lens
+                );
+                for i in 0..lens[0] {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    let row: &[&dyn Display] = &[$(&$name[i]),*];
+                    join(f, row, ' ')?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_columns!(A B);
+impl_columns!(A B C);
+impl_columns!(A B C D);
+impl_columns!(A B C D E);
+
+/// Prints the elements with the given number of them in each line, separated by spaces.
+///
+/// `Chunks(vec![1, 2, 3, 4, 5], 2)` is printed as `1 2`, `3 4` and `5` in three lines.
+pub struct Chunks<T>(pub T, pub usize);
+
+impl<T> Display for Chunks<T>
+where
+    for<'a> &'a T: IntoIterator,
+    for<'a> <&'a T as IntoIterator>::Item: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        assert!(
+            self.1 > 0,
+            "the number of elements in a line must be positive"
+        );
+        for (i, x) in (&self.0).into_iter().enumerate() {
+            if i > 0 {
+                if i % self.1 == 0 {
+                    writeln!(f)?;
+                } else {
+                    write!(f, " ")?;
+                }
+            }
+            x.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints 0-indexed values as 1-indexed ones, with one added to every integer in them.
+///
+/// Integers, tuples, `Vec<T>`, arrays and slices of them are supported. The fields of a tuple are separated by spaces, and the elements of a vector are separated by spaces, or by newlines if they are vectors or tuples themselves. `AddOne(vec![0, 2])` is printed as `1 3`, and `AddOne(vec![(0, 1), (1, 2)])` as `1 2` and `2 3` in two lines.
+pub struct AddOne<T>(pub T);
+
+impl<T: OneBased> Display for AddOne<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_one_based(f)
+    }
+}
+
+/// Values that [`AddOne`] can print.
+pub trait OneBased {
+    /// Whether the value is printed in a line of its own when it is an element of a vector.
+    const LINE: bool = false;
+
+    /// Prints the value with one added to every integer in it.
+    fn fmt_one_based(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+macro_rules! impl_one_based_int {
+    ($($t:ty)*) => {$(
+        impl OneBased for $t {
+            fn fmt_one_based(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                (*self + 1).fmt(f)
+            }
+        }
+    )*};
+}
+
+impl_one_based_int!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+impl<T: OneBased + ?Sized> OneBased for &T {
+    const LINE: bool = T::LINE;
+
+    fn fmt_one_based(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt_one_based(f)
+    }
+}
+
+impl<T: OneBased> OneBased for [T] {
+    const LINE: bool = true;
+
+    fn fmt_one_based(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, x) in self.iter().enumerate() {
+            if i > 0 {
+                if T::LINE {
+                    writeln!(f)?;
+                } else {
+                    write!(f, " ")?;
+                }
+            }
+            x.fmt_one_based(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: OneBased> OneBased for Vec<T> {
+    const LINE: bool = true;
+
+    fn fmt_one_based(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self[..].fmt_one_based(f)
+    }
+}
+
+impl<T: OneBased, const N: usize> OneBased for [T; N] {
+    const LINE: bool = true;
+
+    fn fmt_one_based(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self[..].fmt_one_based(f)
+    }
+}
+
+macro_rules! impl_one_based_tuple {
+    ($first:ident $($name:ident)*) => {
+        impl<$first: OneBased, $($name: OneBased),*> OneBased for ($first, $($name,)*) {
+            const LINE: bool = true;
+
+            #[allow(non_snake_case)]
+            fn fmt_one_based(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let ($first, $($name,)*) = self;
+                $first.fmt_one_based(f)?;
+                $(
+                    write!(f, " ")?;
+                    $name.fmt_one_based(f)?;
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_one_based_tuple!(A B);
+impl_one_based_tuple!(A B C);
+impl_one_based_tuple!(A B C D);
+
+/// Prints a sequence of `bool` as a string of `0` and `1`.
+///
+/// `Bits(vec![true, false, true])` is printed as `101`.
+pub struct Bits<T>(pub T);
+
+impl<T> Display for Bits<T>
+where
+    for<'a> &'a T: IntoIterator<Item = &'a bool>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &b in &self.0 {
+            f.write_str(if b { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints the lowest bits of an integer as a string of `0` and `1` of the given width, from the lowest bit.
+///
+/// The `i`-th character is the `i`-th bit, so that the string tells which items are in the set represented by the mask. `BitMask(0b0110, 5)` is printed as `01100`.
+pub struct BitMask<T>(pub T, pub usize);
+
+macro_rules! impl_bit_mask {
+    ($($t:ty)*) => {$(
+        impl Display for BitMask<$t> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                assert!(
+                    self.1 <= <$t>::BITS as usize,
+                    "the width {} exceeds the bits of {}",
+                    self.1,
+                    stringify!($t)
+                );
+                for i in 0..self.1 {
+                    f.write_str(if self.0 >> i & 1 == 1 { "1" } else { "0" })?;
+                }
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_bit_mask!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+/// Prints the number of the edges, followed by each edge `u v` (or `u v w` with a weight) in a line.
+///
+/// The second field is added to the vertices, which is `1` to print 0-indexed vertices as 1-indexed ones. `Edges(vec![(0, 1), (1, 2)], 1)` is printed as `2`, `1 2` and `2 3` in three lines.
+pub struct Edges<T>(pub T, pub usize);
+
+impl Display for Edges<Vec<(usize, usize)>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.len())?;
+        for &(u, v) in &self.0 {
+            write!(f, "\n{} {}", u + self.1, v + self.1)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Display> Display for Edges<Vec<(usize, usize, W)>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.len())?;
+        for (u, v, w) in &self.0 {
+            write!(f, "\n{} {} {}", u + self.1, v + self.1, w)?;
+        }
+        Ok(())
+    }
+}
+
+fn join<I>(f: &mut fmt::Formatter<'_>, iter: I, sep: impl Display) -> fmt::Result
+where
+    I: IntoIterator,
+    I::Item: Display,
+{
+    for (i, x) in iter.into_iter().enumerate() {
+        if i > 0 {
+            sep.fmt(f)?;
+        }
+        x.fmt(f)?;
+    }
+    Ok(())
+}
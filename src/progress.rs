@@ -0,0 +1,54 @@
+//! Progress display of `#[argio(progress)]`.
+//!
+//! The generated code marks each finished case, and the progress is shown on stderr when it is a terminal.
+
+use crate::diag::{tag, Level};
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Shows the number of finished cases.
+pub struct Progress {
+    total: Option<usize>,
+    done: AtomicUsize,
+    enabled: bool,
+}
+
+impl Progress {
+    /// Creates a progress display for `total` cases, or an unknown number of cases if `None`.
+    pub fn new(total: Option<usize>) -> Self {
+        Progress {
+            total,
+            done: AtomicUsize::new(0),
+            enabled: io::stderr().is_terminal(),
+        }
+    }
+
+    /// Marks that a case has finished.
+    pub fn done(&self) {
+        let done = self.done.fetch_add(1, Ordering::SeqCst) + 1;
+        if !self.enabled {
+            return;
+        }
+        let mut stderr = io::stderr().lock();
+        let _ = match self.total {
+            Some(total) => write!(
+                stderr,
+                "\r{} case {}/{} done",
+                tag(Level::Info),
+                done,
+                total
+            ),
+            None => write!(stderr, "\r{} case {} done", tag(Level::Info), done),
+        };
+        let _ = stderr.flush();
+    }
+
+    /// Ends the progress display.
+    pub fn finish(&self) {
+        if self.enabled && self.done.load(Ordering::SeqCst) > 0 {
+            eprintln!();
+        }
+    }
+}
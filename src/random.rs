@@ -0,0 +1,204 @@
+//! A small random number generator for the generators of test inputs.
+
+use std::{
+    env,
+    ops::{Bound, RangeBounds},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A random number generator seeded by a `u64`.
+///
+/// The same seed always gives the same sequence, so a failing input can be reproduced from its
+/// seed.
+///
+/// ```
+/// let mut rng = argio::random::Rng::new(42);
+/// let n = rng.range(1..=10);
+/// assert!((1..=10).contains(&n));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+    // The values to return instead of random ones, used to shrink a failing input.
+    replay: Option<Vec<u64>>,
+    draws: Vec<u64>,
+}
+
+impl Rng {
+    /// Creates a generator from `seed`.
+    pub fn new(seed: u64) -> Self {
+        Rng {
+            state: seed,
+            replay: None,
+            draws: vec![],
+        }
+    }
+
+    // Creates a generator returning `draws` from `next_u64`, and then zeros.
+    pub(crate) fn replay(draws: Vec<u64>) -> Self {
+        Rng {
+            state: 0,
+            replay: Some(draws),
+            draws: vec![],
+        }
+    }
+
+    // Returns the values returned from `next_u64` so far.
+    pub(crate) fn into_draws(self) -> Vec<u64> {
+        self.draws
+    }
+
+    /// Returns a random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        let ret = match &self.replay {
+            Some(replay) => replay.get(self.draws.len()).copied().unwrap_or(0),
+            None => {
+                // SplitMix64
+                self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                let mut z = self.state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                z ^ (z >> 31)
+            }
+        };
+        self.draws.push(ret);
+        ret
+    }
+
+    /// Returns a random value in `range`.
+    ///
+    /// Panics if `range` is empty, or if it is unbounded for floating point numbers.
+    pub fn range<T: Uniform>(&mut self, range: impl RangeBounds<T>) -> T {
+        T::sample(self, range)
+    }
+
+    /// Returns a random `f64` in `[0, 1)`.
+    pub fn f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random element of `slice`.
+    ///
+    /// Panics if `slice` is empty.
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> &'a T {
+        &slice[self.range(..slice.len())]
+    }
+
+    /// Shuffles `slice` uniformly.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            slice.swap(i, self.range(..=i));
+        }
+    }
+}
+
+/// Returns the seed for the program generated by `#[argio::generator]`.
+///
+/// The seed is given by the first command line argument or the environment variable `ARGIO_SEED`. If neither is given, it is taken from the current time.
+pub fn seed() -> u64 {
+    match env::args().nth(1).or_else(|| env::var("ARGIO_SEED").ok()) {
+        Some(seed) => seed
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid seed: `{}`", seed)),
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64),
+    }
+}
+
+/// Types supported by [`Rng::range`].
+pub trait Uniform: Sized {
+    #[doc(hidden)]
+    fn sample(rng: &mut Rng, range: impl RangeBounds<Self>) -> Self;
+}
+
+// Returns the inclusive bounds of an integer range.
+fn int_bounds<T: Copy + Into<i128>>(range: &impl RangeBounds<T>, min: T, max: T) -> (i128, i128) {
+    let lo = match range.start_bound() {
+        Bound::Included(&x) => x.into(),
+        Bound::Excluded(&x) => x.into() + 1,
+        Bound::Unbounded => min.into(),
+    };
+    let hi = match range.end_bound() {
+        Bound::Included(&x) => x.into(),
+        Bound::Excluded(&x) => x.into() - 1,
+        Bound::Unbounded => max.into(),
+    };
+    assert!(lo <= hi, "empty range");
+    (lo, hi)
+}
+
+// The value is monotonic in the random `u64`, so that smaller ones give smaller values when
+// shrinking an input.
+fn sample_int(rng: &mut Rng, (lo, hi): (i128, i128)) -> i128 {
+    let width = (hi - lo) as u128 + 1;
+    lo + ((rng.next_u64() as u128 * width) >> 64) as i128
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(
+            impl Uniform for $t {
+                fn sample(rng: &mut Rng, range: impl RangeBounds<Self>) -> Self {
+                    let bounds = int_bounds(&range, <$t>::MIN, <$t>::MAX);
+                    sample_int(rng, bounds) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+macro_rules! impl_size {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl Uniform for $t {
+                fn sample(rng: &mut Rng, range: impl RangeBounds<Self>) -> Self {
+                    let map = |b: Bound<&$t>| b.map(|&x| x as $u);
+                    let range = (map(range.start_bound()), map(range.end_bound()));
+                    <$u>::sample(rng, range) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_size!(isize => i64, usize => u64);
+
+macro_rules! impl_float {
+    ($($t:ty),*) => {
+        $(
+            impl Uniform for $t {
+                fn sample(rng: &mut Rng, range: impl RangeBounds<Self>) -> Self {
+                    let (lo, hi) = match (range.start_bound(), range.end_bound()) {
+                        (Bound::Included(&lo) | Bound::Excluded(&lo), Bound::Included(&hi) | Bound::Excluded(&hi)) => (lo, hi),
+                        _ => panic!("unbounded range of floating point numbers"),
+                    };
+                    assert!(lo <= hi, "empty range");
+                    lo + (hi - lo) * rng.f64() as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_float!(f32, f64);
+
+impl Uniform for char {
+    fn sample(rng: &mut Rng, range: impl RangeBounds<Self>) -> Self {
+        let map = |b: Bound<&char>| b.map(|&c| c as u32);
+        let end = match range.end_bound() {
+            Bound::Unbounded => Bound::Included(char::MAX as u32),
+            end => map(end),
+        };
+        let range = (map(range.start_bound()), end);
+        loop {
+            // Skips the surrogates, which are not chars.
+            if let Some(c) = char::from_u32(u32::sample(rng, range)) {
+                return c;
+            }
+        }
+    }
+}
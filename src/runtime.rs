@@ -0,0 +1,19 @@
+//! Tokio runtime of `#[argio(runtime = tokio)]`.
+//!
+//! The runtime is created on the first use and shared by all the cases.
+
+use std::{future::Future, sync::OnceLock};
+use tokio::runtime::{Builder, Runtime};
+
+fn runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| match Builder::new_multi_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(err) => panic!("failed to build the tokio runtime: {}", err),
+    })
+}
+
+/// Runs `future` to completion on the shared runtime.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    runtime().block_on(future)
+}
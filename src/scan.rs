@@ -0,0 +1,611 @@
+//! A minimal built-in input backend.
+//!
+//! This module provides a small scanner that implements the subset of the `input!` grammar of `proconio` that `#[argio]` needs. It has no dependencies, so it can be used where `proconio` is not available.
+
+use std::{
+    any::type_name,
+    convert::TryInto,
+    fmt::Debug,
+    io::{self, BufRead, BufReader, Read, Stdin},
+    str::{self, FromStr},
+    sync::{Mutex, MutexGuard, OnceLock},
+};
+
+/// A source of whitespace separated tokens.
+pub trait Source {
+    /// Returns the next token, or `None` if the input is exhausted.
+    fn next_token(&mut self) -> Option<&str>;
+
+    /// Returns `true` if no tokens are left.
+    #[allow(clippy::wrong_self_convention)]
+    fn is_empty(&mut self) -> bool;
+
+    /// Returns `true` if a blank line comes before the next token or no tokens are left, and skips over the blank lines.
+    ///
+    /// Sources without the information of lines only stop at the end of the input.
+    fn blank_line(&mut self) -> bool {
+        self.is_empty()
+    }
+
+    /// Returns the next line without the line break, or `None` if the input is exhausted.
+    ///
+    /// If the line of the last token has more tokens, the rest of it is returned. Sources without the information of lines panic.
+    fn next_line(&mut self) -> Option<&str> {
+        panic!("this input source cannot read lines")
+    }
+
+    /// Returns the next token, panicking if the input is exhausted.
+    fn next_token_unwrap(&mut self) -> &str {
+        self.next_token()
+            .expect("failed to get the next token; maybe reader reached an end of input")
+    }
+}
+
+impl<S: Source + ?Sized> Source for &'_ mut S {
+    fn next_token(&mut self) -> Option<&str> {
+        (**self).next_token()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        (**self).is_empty()
+    }
+
+    fn blank_line(&mut self) -> bool {
+        (**self).blank_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        (**self).next_line()
+    }
+}
+
+/// A scanner that reads tokens from a `BufRead` line by line.
+pub struct Scanner<R> {
+    reader: R,
+    line: String,
+    pos: usize,
+    normalize: bool,
+    first: bool,
+    line_no: usize,
+    line_start: usize,
+    token_pos: (usize, usize),
+    // The line of the last token or the last blank line skipped.
+    section_line: usize,
+    report: bool,
+}
+
+impl<R: BufRead> Scanner<R> {
+    /// Creates a new scanner reading from `reader`.
+    pub fn new(reader: R) -> Self {
+        Scanner {
+            reader,
+            line: String::new(),
+            pos: 0,
+            normalize: false,
+            first: true,
+            line_no: 1,
+            line_start: 0,
+            token_pos: (0, 0),
+            section_line: 0,
+            report: false,
+        }
+    }
+
+    /// Creates a new scanner that reads all of `reader` into a buffer up front.
+    pub fn once(reader: R) -> Self {
+        let mut ret = Scanner::new(reader);
+        if let Err(err) = ret.reader.read_to_string(&mut ret.line) {
+            panic!("failed to read the input: {}", err);
+        }
+        ret
+    }
+
+    /// Makes the scanner report the position of each token to the diagnostics of `#[argio]`.
+    #[doc(hidden)]
+    pub fn report(mut self) -> Self {
+        self.report = true;
+        self
+    }
+
+    /// Returns the line and the column (both 1-origin) of the last token.
+    pub fn position(&self) -> (usize, usize) {
+        self.token_pos
+    }
+
+    /// Makes the scanner tolerant of input files saved in other environments.
+    ///
+    /// A leading byte order mark and all `\r` characters are removed before tokenizing.
+    pub fn normalize(mut self) -> Self {
+        self.normalize = true;
+        self.normalize_line();
+        self
+    }
+
+    fn normalize_line(&mut self) {
+        if !self.normalize || self.pos != 0 {
+            return;
+        }
+        if self.first && self.line.starts_with('\u{feff}') {
+            self.line.drain(..'\u{feff}'.len_utf8());
+        }
+        if !self.line.is_empty() {
+            self.first = false;
+        }
+        self.line.retain(|c| c != '\r');
+    }
+
+    /// Panics if any tokens are left in the input.
+    pub fn finish(&mut self) {
+        finish(self);
+    }
+
+    fn fill(&mut self) -> bool {
+        loop {
+            let rest = &self.line[self.pos..];
+            let skipped = rest.len() - rest.trim_start().len();
+            for (i, b) in rest[..skipped].bytes().enumerate() {
+                if b == b'\n' {
+                    self.line_no += 1;
+                    self.line_start = self.pos + i + 1;
+                }
+            }
+            self.pos += skipped;
+            if self.pos < self.line.len() {
+                return true;
+            }
+
+            self.line.clear();
+            self.pos = 0;
+            self.line_start = 0;
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return false,
+                Ok(_) => self.normalize_line(),
+                Err(err) => panic!("failed to read the input: {}", err),
+            }
+        }
+    }
+}
+
+// Panics if any tokens are left in `source`.
+fn finish(source: &mut impl Source) {
+    let mut rest = vec![];
+    let mut count = 0;
+    while let Some(token) = source.next_token() {
+        if rest.len() < 10 {
+            rest.push(token.to_string());
+        }
+        count += 1;
+    }
+    if count > 0 {
+        panic!(
+            "{} unread token(s) left in the input: {}{}",
+            count,
+            rest.join(" "),
+            if count > rest.len() { " ..." } else { "" }
+        );
+    }
+}
+
+impl<'a> From<&'a str> for Scanner<&'a [u8]> {
+    fn from(s: &'a str) -> Self {
+        Scanner::new(s.as_bytes())
+    }
+}
+
+impl<R: BufRead> Source for Scanner<R> {
+    fn next_token(&mut self) -> Option<&str> {
+        if !self.fill() {
+            return None;
+        }
+
+        self.token_pos = (self.line_no, self.pos - self.line_start + 1);
+        self.section_line = self.line_no;
+        if self.report {
+            crate::diag::set_position(self.token_pos);
+        }
+        crate::io::count_token();
+
+        let rest = &self.line[self.pos..];
+        let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let start = self.pos;
+        self.pos += len;
+        Some(&self.line[start..start + len])
+    }
+
+    fn is_empty(&mut self) -> bool {
+        !self.fill()
+    }
+
+    fn blank_line(&mut self) -> bool {
+        if !self.fill() {
+            return true;
+        }
+        if self.line_no < self.section_line + 2 {
+            return false;
+        }
+        self.section_line = self.line_no - 1;
+        true
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        // The rest of the line of the last token is skipped if it is blank.
+        let rest = &self.line[self.pos..];
+        let len = rest.find('\n').map_or(rest.len(), |i| i + 1);
+        if self.pos > self.line_start && rest[..len].trim().is_empty() {
+            if rest[..len].ends_with('\n') {
+                self.line_no += 1;
+                self.line_start = self.pos + len;
+            }
+            self.pos += len;
+        }
+        if self.pos == self.line.len() {
+            self.line.clear();
+            self.pos = 0;
+            self.line_start = 0;
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => self.normalize_line(),
+                Err(err) => panic!("failed to read the input: {}", err),
+            }
+        }
+
+        let mut start = self.pos;
+        if start > self.line_start {
+            let rest = &self.line[start..];
+            start += rest.len() - rest.trim_start().len();
+        }
+        self.token_pos = (self.line_no, start - self.line_start + 1);
+        self.section_line = self.line_no;
+        if self.report {
+            crate::diag::set_position(self.token_pos);
+        }
+
+        let len = self.line[start..].find('\n');
+        self.pos = len.map_or(self.line.len(), |len| start + len + 1);
+        if len.is_some() {
+            self.line_no += 1;
+            self.line_start = self.pos;
+        }
+        let end = len.map_or(self.line.len(), |len| start + len);
+        Some(self.line[start..end].trim_end_matches('\r'))
+    }
+}
+
+#[cfg(feature = "proconio")]
+impl<R: BufRead> proconio::source::Source<R> for Scanner<R> {
+    fn next_token(&mut self) -> Option<&str> {
+        Source::next_token(self)
+    }
+
+    fn is_empty(&mut self) -> bool {
+        Source::is_empty(self)
+    }
+}
+
+/// A scanner that reads all of the input into a byte buffer up front, and splits it at ASCII whitespace.
+///
+/// Unlike [`Scanner::once`], the input is neither validated as UTF-8 as a whole nor copied into strings. Only each token is checked when it is taken, which costs almost nothing for numbers, and numbers are parsed from the slice of the buffer. The end of each token is searched for eight bytes at a time. This is the fastest way to read a huge input.
+pub struct ByteScanner {
+    buf: Vec<u8>,
+    pos: usize,
+    line_no: usize,
+    line_start: usize,
+    token_pos: (usize, usize),
+    // The line of the last token or the last blank line skipped.
+    section_line: usize,
+    report: bool,
+}
+
+impl ByteScanner {
+    /// Creates a new scanner that reads all of `reader` into a buffer.
+    pub fn new(mut reader: impl Read) -> Self {
+        let mut buf = vec![];
+        if let Err(err) = reader.read_to_end(&mut buf) {
+            panic!("failed to read the input: {}", err);
+        }
+        ByteScanner {
+            buf,
+            pos: 0,
+            line_no: 1,
+            line_start: 0,
+            token_pos: (0, 0),
+            section_line: 0,
+            report: false,
+        }
+    }
+
+    /// Makes the scanner report the position of each token to the diagnostics of `#[argio]`.
+    #[doc(hidden)]
+    pub fn report(mut self) -> Self {
+        self.report = true;
+        self
+    }
+
+    /// Returns the line and the column (both 1-origin) of the last token.
+    pub fn position(&self) -> (usize, usize) {
+        self.token_pos
+    }
+
+    /// Makes the scanner tolerant of input files saved in other environments.
+    ///
+    /// A leading byte order mark is skipped. `\r` is skipped as whitespace in any case.
+    pub fn normalize(mut self) -> Self {
+        if self.buf[self.pos..].starts_with("\u{feff}".as_bytes()) {
+            self.pos += "\u{feff}".len();
+            self.line_start = self.pos;
+        }
+        self
+    }
+
+    /// Panics if any tokens are left in the input.
+    pub fn finish(&mut self) {
+        finish(self);
+    }
+
+    /// Returns the next token as bytes, or `None` if the input is exhausted.
+    pub fn next_bytes(&mut self) -> Option<&[u8]> {
+        if !self.skip_whitespace() {
+            return None;
+        }
+
+        self.token_pos = (self.line_no, self.pos - self.line_start + 1);
+        self.section_line = self.line_no;
+        if self.report {
+            crate::diag::set_position(self.token_pos);
+        }
+        crate::io::count_token();
+
+        let start = self.pos;
+        self.pos += find_whitespace(&self.buf[start..]);
+        Some(&self.buf[start..self.pos])
+    }
+
+    // Skips whitespace before the next token, and returns `false` if no tokens are left.
+    fn skip_whitespace(&mut self) -> bool {
+        while self.pos < self.buf.len() && self.buf[self.pos].is_ascii_whitespace() {
+            if self.buf[self.pos] == b'\n' {
+                self.line_no += 1;
+                self.line_start = self.pos + 1;
+            }
+            self.pos += 1;
+        }
+        self.pos < self.buf.len()
+    }
+}
+
+// Returns the index of the first ASCII whitespace in `buf`, or its length if there is none.
+//
+// Eight bytes are checked at a time for a byte less than `0x21`, as all the ASCII whitespace are,
+// and only the byte found is checked exactly.
+// This does not depend on `memchr`, as this module is also embedded into the files written by
+// `#[argio(bundle)]`. See `benches/scan.rs` for the comparison with the search of a byte at a time.
+fn find_whitespace(buf: &[u8]) -> usize {
+    const ONES: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HIGHS: u64 = u64::from_ne_bytes([0x80; 8]);
+    let mut i = 0;
+    while let Some(bytes) = buf.get(i..i + 8) {
+        let word = u64::from_le_bytes(bytes.try_into().unwrap());
+        // The high bit of a byte is set if it is less than `0x21`. Only the lowest one is exact,
+        // because a borrow may set the bits of the bytes above it.
+        let found = word.wrapping_sub(ONES * 0x21) & !word & HIGHS;
+        if found == 0 {
+            i += 8;
+            continue;
+        }
+        let j = i + found.trailing_zeros() as usize / 8;
+        if buf[j].is_ascii_whitespace() {
+            return j;
+        }
+        i = j + 1;
+    }
+    buf[i..]
+        .iter()
+        .position(u8::is_ascii_whitespace)
+        .map_or(buf.len(), |j| i + j)
+}
+
+impl Source for ByteScanner {
+    fn next_token(&mut self) -> Option<&str> {
+        let token = self.next_bytes()?;
+        match str::from_utf8(token) {
+            Ok(token) => Some(token),
+            Err(err) => panic!("the input is not valid UTF-8: {}", err),
+        }
+    }
+
+    fn is_empty(&mut self) -> bool {
+        let rest = &self.buf[self.pos..];
+        rest.iter().all(u8::is_ascii_whitespace)
+    }
+
+    fn blank_line(&mut self) -> bool {
+        if !self.skip_whitespace() {
+            return true;
+        }
+        if self.line_no < self.section_line + 2 {
+            return false;
+        }
+        self.section_line = self.line_no - 1;
+        true
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        // The rest of the line of the last token is skipped if it is blank.
+        let rest = &self.buf[self.pos..];
+        let len = rest
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(rest.len(), |i| i + 1);
+        if self.pos > self.line_start && rest[..len].iter().all(u8::is_ascii_whitespace) {
+            if rest[..len].ends_with(b"\n") {
+                self.line_no += 1;
+                self.line_start = self.pos + len;
+            }
+            self.pos += len;
+        }
+        if self.pos == self.buf.len() {
+            return None;
+        }
+
+        let mut start = self.pos;
+        if start > self.line_start {
+            while self.buf[start].is_ascii_whitespace() {
+                start += 1;
+            }
+        }
+        self.token_pos = (self.line_no, start - self.line_start + 1);
+        self.section_line = self.line_no;
+        if self.report {
+            crate::diag::set_position(self.token_pos);
+        }
+
+        let len = self.buf[start..].iter().position(|&b| b == b'\n');
+        self.pos = len.map_or(self.buf.len(), |len| start + len + 1);
+        if len.is_some() {
+            self.line_no += 1;
+            self.line_start = self.pos;
+        }
+        let end = len.map_or(self.buf.len(), |len| start + len);
+        let line = &self.buf[start..end];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        match str::from_utf8(line) {
+            Ok(line) => Some(line),
+            Err(err) => panic!("the input is not valid UTF-8: {}", err),
+        }
+    }
+}
+
+// The reader type is only for `proconio` to infer it, as the input is read already.
+#[cfg(feature = "proconio")]
+impl proconio::source::Source<io::Empty> for ByteScanner {
+    fn next_token(&mut self) -> Option<&str> {
+        Source::next_token(self)
+    }
+
+    fn is_empty(&mut self) -> bool {
+        Source::is_empty(self)
+    }
+}
+
+/// A type that can be read from a `Source`.
+pub trait Scan {
+    type Output;
+
+    fn scan<S: Source + ?Sized>(source: &mut S) -> Self::Output;
+}
+
+impl<T: FromStr> Scan for T
+where
+    T::Err: Debug,
+{
+    type Output = T;
+
+    fn scan<S: Source + ?Sized>(source: &mut S) -> T {
+        let token = source.next_token_unwrap();
+        match token.parse() {
+            Ok(v) => v,
+            Err(err) => panic!(
+                "failed to parse the input `{}` to the value of type `{}`: {:?}",
+                token,
+                type_name::<T>(),
+                err
+            ),
+        }
+    }
+}
+
+static STDIN: OnceLock<Mutex<Scanner<BufReader<Stdin>>>> = OnceLock::new();
+
+#[doc(hidden)]
+pub fn stdin() -> MutexGuard<'static, Scanner<BufReader<Stdin>>> {
+    STDIN
+        .get_or_init(|| Mutex::new(Scanner::new(BufReader::new(io::stdin()))))
+        .lock()
+        .unwrap()
+}
+
+/// Reads values from the standard input or the given source.
+///
+/// This accepts the same syntax as `proconio::input!` except for its `derive` support: scalars, `[T; n]`, length prefixed `[T]`, tuples and the markers in [`argio::marker`](crate::marker).
+///
+/// ```
+/// # use argio::{scan, marker::Usize1, scan::Scanner};
+/// let mut source = Scanner::from("3 1 2 3 abc 2");
+/// scan! {
+///     from &mut source,
+///     n: usize,
+///     mut a: [i64; n],
+///     s: String,
+///     k: Usize1,
+/// }
+/// a.reverse();
+/// assert_eq!(a, vec![3, 2, 1]);
+/// assert_eq!(s, "abc");
+/// assert_eq!(k, 1);
+/// ```
+#[macro_export]
+macro_rules! scan {
+    (from $source:expr, $($rest:tt)*) => {
+        #[allow(unused_mut)]
+        let mut source = $source;
+        $crate::__scan_inner!(@source [source] @rest $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        let mut locked_stdin = $crate::scan::stdin();
+        $crate::__scan_inner!(@source [&mut *locked_stdin] @rest $($rest)*);
+        drop(locked_stdin);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __scan_inner {
+    (@source [$source:expr] @rest) => {};
+    (@source [$source:expr] @rest , $($rest:tt)*) => {
+        $crate::__scan_inner!(@source [$source] @rest $($rest)*);
+    };
+    (@source [$source:expr] @rest mut $var:ident : $($rest:tt)*) => {
+        $crate::__scan_inner!(@source [$source] @mut [mut] @var $var @kind [] @rest $($rest)*);
+    };
+    (@source [$source:expr] @rest $var:ident : $($rest:tt)*) => {
+        $crate::__scan_inner!(@source [$source] @mut [] @var $var @kind [] @rest $($rest)*);
+    };
+
+    (@source [$source:expr] @mut [$($mut:tt)?] @var $var:ident @kind [$($kind:tt)*] @rest) => {
+        let $($mut)? $var = $crate::__scan_value!(@source [$source] @kind [$($kind)*]);
+    };
+    (@source [$source:expr] @mut [$($mut:tt)?] @var $var:ident @kind [$($kind:tt)*] @rest , $($rest:tt)*) => {
+        $crate::__scan_inner!(@source [$source] @mut [$($mut)?] @var $var @kind [$($kind)*] @rest);
+        $crate::__scan_inner!(@source [$source] @rest $($rest)*);
+    };
+    (@source [$source:expr] @mut [$($mut:tt)?] @var $var:ident @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::__scan_inner!(@source [$source] @mut [$($mut)?] @var $var @kind [$($kind)* $tt] @rest $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __scan_value {
+    (@source [$source:expr] @kind [[$($kind:tt)*]]) => {
+        $crate::__scan_value!(@array @source [$source] @kind [] @rest $($kind)*)
+    };
+    (@source [$source:expr] @kind [($($kind:tt),*)]) => {
+        ($($crate::__scan_value!(@source [$source] @kind [$kind])),*)
+    };
+    (@source [$source:expr] @kind [$kind:ty]) => {
+        <$kind as $crate::scan::Scan>::scan(&mut $source)
+    };
+
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest) => {{
+        let len = <usize as $crate::scan::Scan>::scan(&mut $source);
+        $crate::__scan_value!(@source [$source] @kind [[$($kind)*; len]])
+    }};
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest ; $len:expr) => {
+        (0..$len)
+            .map(|_| $crate::__scan_value!(@source [$source] @kind [$($kind)*]))
+            .collect::<Vec<_>>()
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::__scan_value!(@array @source [$source] @kind [$($kind)* $tt] @rest $($rest)*)
+    };
+}
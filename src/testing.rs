@@ -0,0 +1,516 @@
+//! Checks of the tests generated by `#[argio]` and its companion attributes.
+
+use crate::{
+    judge::{ChannelReader, Judge},
+    random::Rng,
+};
+use std::{
+    any::Any,
+    env,
+    fmt::{Display, Write},
+    fs,
+    io::BufReader,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::mpsc,
+    thread,
+};
+
+/// Panics if `actual` does not match `expected`.
+///
+/// Trailing whitespace of each line and trailing empty lines are ignored, as most judges do.
+#[track_caller]
+pub fn check(input: &str, expected: &str, actual: &str) {
+    if let Err(reason) = compare(expected, actual) {
+        fail(input, expected, actual, reason);
+    }
+}
+
+/// Panics if `actual` does not match `expected`, comparing tokens separated by whitespace.
+///
+/// Tokens which are both numbers are accepted if either the absolute or the relative error is
+/// within `tolerance`. Other tokens must be equal.
+#[track_caller]
+pub fn check_float(input: &str, expected: &str, actual: &str, tolerance: f64) {
+    if let Err(reason) = compare_float(expected, actual, tolerance) {
+        fail(input, expected, actual, reason);
+    }
+}
+
+/// Panics if `actual` is not accepted by a checker.
+#[track_caller]
+pub fn check_with(input: &str, expected: &str, actual: &str, accepted: bool) {
+    if !accepted {
+        fail(
+            input,
+            expected,
+            actual,
+            Some("rejected by the checker".to_string()),
+        );
+    }
+}
+
+/// Panics if `actual` is not accepted for the expected output in the snapshot file at `path`.
+///
+/// If the environment variable `ARGIO_UPDATE` is set to `1`, `actual` is written to the file
+/// instead.
+#[track_caller]
+pub fn check_snapshot(
+    path: &str,
+    input: &str,
+    actual: &str,
+    accept: impl Fn(&str, &str, &str) -> bool,
+) {
+    if update_snapshot(path, actual) {
+        return;
+    }
+    let expected = read_snapshot(path);
+    if !accept(input, &expected, actual) {
+        panic!(
+            "output does not match the snapshot `{}`\n--- input\n{}\n--- diff (-expected +actual)\n{}",
+            path,
+            input.trim_end(),
+            diff(&expected, actual).trim_end()
+        );
+    }
+}
+
+/// Runs `solve` on `iters` inputs made by `gen` as [`stress`] does, and compares the outputs with
+/// the snapshot file at `path`.
+///
+/// If the environment variable `ARGIO_UPDATE` is set to `1`, the outputs are written to the file
+/// instead.
+#[track_caller]
+pub fn stress_snapshot<T: Display>(
+    path: &str,
+    iters: u64,
+    mut gen: impl FnMut(&mut Rng) -> T,
+    solve: impl Fn(&str) -> String,
+    accept: impl Fn(&str, &str, &str) -> bool,
+) {
+    const SEPARATOR: &str = "=== seed ";
+
+    let mut cases = vec![];
+    for seed in 0..iters {
+        let input = gen(&mut Rng::new(seed)).to_string();
+        let output = match run_quietly(|| solve(&input)) {
+            Some(output) => output,
+            None => panic!(
+                "the program panicked with seed {}\n--- input\n{}",
+                seed,
+                input.trim_end()
+            ),
+        };
+        cases.push((input, output));
+    }
+
+    let mut snapshot = String::new();
+    for (seed, (_, output)) in cases.iter().enumerate() {
+        writeln!(snapshot, "{}{}", SEPARATOR, seed).unwrap();
+        snapshot.push_str(output);
+        if !output.is_empty() && !output.ends_with('\n') {
+            snapshot.push('\n');
+        }
+    }
+    if update_snapshot(path, &snapshot) {
+        return;
+    }
+
+    let expected = read_snapshot(path);
+    let mut expected_outputs = vec![];
+    for line in expected.split_inclusive('\n') {
+        if line.starts_with(SEPARATOR) {
+            expected_outputs.push(String::new());
+        } else if let Some(output) = expected_outputs.last_mut() {
+            output.push_str(line);
+        }
+    }
+    if expected_outputs.len() as u64 != iters {
+        panic!(
+            "the snapshot `{}` has {} cases, but iters is {}; set ARGIO_UPDATE=1 to update it",
+            path,
+            expected_outputs.len(),
+            iters
+        );
+    }
+    for (seed, ((input, actual), expected)) in cases.iter().zip(&expected_outputs).enumerate() {
+        if !accept(input, expected, actual) {
+            panic!(
+                "output does not match the snapshot `{}` with seed {}\n--- input\n{}\n--- diff (-expected +actual)\n{}",
+                path,
+                seed,
+                input.trim_end(),
+                diff(expected, actual).trim_end()
+            );
+        }
+    }
+}
+
+/// Checks the outputs for the inputs recorded by `#[argio(record)]` in the directory `dir`.
+///
+/// Each `*.in` file in `dir` is solved by `solve`, and the output is compared with the `.out` file next to it by `accept`. It does nothing if `dir` does not exist.
+pub fn replay(
+    dir: &str,
+    solve: impl Fn(&str) -> String,
+    accept: impl Fn(&str, &str, &str) -> bool,
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    let mut inputs = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "in"))
+        .collect::<Vec<_>>();
+    inputs.sort();
+    for path in inputs {
+        let read = |path: &Path| {
+            fs::read_to_string(path)
+                .unwrap_or_else(|err| panic!("failed to read `{}`: {}", path.display(), err))
+        };
+        let input = read(&path);
+        let expected = read(&path.with_extension("out"));
+        let actual = solve(&input);
+        if !accept(&input, &expected, &actual) {
+            panic!(
+                "output differs from the recorded one for `{}`\n--- input\n{}\n--- diff (-expected +actual)\n{}",
+                path.display(),
+                input.trim_end(),
+                diff(&expected, &actual).trim_end()
+            );
+        }
+    }
+}
+
+// Writes `content` to the snapshot file at `path` if `ARGIO_UPDATE=1`, and returns whether it
+// did.
+#[track_caller]
+fn update_snapshot(path: &str, content: &str) -> bool {
+    if env::var("ARGIO_UPDATE").as_deref() != Ok("1") {
+        return false;
+    }
+    if fs::read_to_string(path).ok().as_deref() != Some(content) {
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir)
+                .unwrap_or_else(|err| panic!("failed to create `{}`: {}", dir.display(), err));
+        }
+        fs::write(path, content)
+            .unwrap_or_else(|err| panic!("failed to write `{}`: {}", path, err));
+    }
+    true
+}
+
+#[track_caller]
+fn read_snapshot(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|err| {
+        panic!(
+            "failed to read the snapshot `{}`: {}; set ARGIO_UPDATE=1 to create it",
+            path, err
+        )
+    })
+}
+
+// Returns the difference of the lines of `expected` and `actual`.
+fn diff(expected: &str, actual: &str) -> String {
+    let a = normalize(expected);
+    let b = normalize(actual);
+    // lcs[i][j] is the length of the longest common subsequence of a[i..] and b[j..].
+    let mut lcs = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ret = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            writeln!(ret, " {}", a[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            writeln!(ret, "-{}", a[i]).unwrap();
+            i += 1;
+        } else {
+            writeln!(ret, "+{}", b[j]).unwrap();
+            j += 1;
+        }
+    }
+    ret
+}
+
+/// Compares the outputs in the same way as [`check`].
+pub fn compare(expected: &str, actual: &str) -> Result<(), Option<String>> {
+    if normalize(expected) == normalize(actual) {
+        Ok(())
+    } else {
+        Err(None)
+    }
+}
+
+/// Compares the outputs in the same way as [`check_float`].
+pub fn compare_float(expected: &str, actual: &str, tolerance: f64) -> Result<(), Option<String>> {
+    let expected_tokens = expected.split_whitespace().collect::<Vec<_>>();
+    let actual_tokens = actual.split_whitespace().collect::<Vec<_>>();
+    if expected_tokens.len() != actual_tokens.len() {
+        return Err(Some(format!(
+            "expected {} tokens, but got {}",
+            expected_tokens.len(),
+            actual_tokens.len()
+        )));
+    }
+
+    for (i, (e, a)) in expected_tokens.iter().zip(&actual_tokens).enumerate() {
+        if e == a {
+            continue;
+        }
+        let ok = match (e.parse::<f64>(), a.parse::<f64>()) {
+            (Ok(e), Ok(a)) => {
+                let diff = (e - a).abs();
+                diff <= tolerance || diff <= tolerance * e.abs()
+            }
+            _ => false,
+        };
+        if !ok {
+            return Err(Some(format!(
+                "token {} differs: expected `{}`, but got `{}`",
+                i + 1,
+                e,
+                a
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[track_caller]
+fn fail(input: &str, expected: &str, actual: &str, reason: Option<String>) -> ! {
+    panic!(
+        "output does not match{}\n--- input\n{}\n--- expected\n{}\n--- actual\n{}",
+        reason.map(|r| format!(" ({})", r)).unwrap_or_default(),
+        input.trim_end(),
+        expected.trim_end(),
+        actual.trim_end()
+    );
+}
+
+fn normalize(s: &str) -> Vec<&str> {
+    let mut lines = s.lines().map(str::trim_end).collect::<Vec<_>>();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines
+}
+
+/// Runs `solve` and `brute` on `iters` inputs made by `gen`, and panics if their outputs differ.
+///
+/// The `i`-th input is made from the seed `i`. The first failing input is shrunk by making
+/// `gen` again with smaller random values, as long as `brute` still succeeds and the outputs
+/// still differ. A panic of `solve` is also reported as a failure.
+#[track_caller]
+pub fn stress<T: Display>(
+    iters: u64,
+    mut gen: impl FnMut(&mut Rng) -> T,
+    solve: impl Fn(&str) -> String,
+    brute: impl Fn(&str) -> String,
+    accept: impl Fn(&str, &str, &str) -> bool,
+) {
+    // Returns the outputs of `brute` and `solve` if `input` is a counterexample.
+    let counterexample = |input: &str| {
+        let expected = run_quietly(|| brute(input))?;
+        match run_quietly(|| solve(input)) {
+            Some(actual) if accept(input, &expected, &actual) => None,
+            Some(actual) => Some((expected, actual)),
+            None => Some((expected, "(panicked)".to_string())),
+        }
+    };
+
+    for seed in 0..iters {
+        let mut rng = Rng::new(seed);
+        let input = gen(&mut rng).to_string();
+        if let Some(outputs) = counterexample(&input) {
+            let (input, (expected, actual)) =
+                shrink(&mut gen, rng.into_draws(), input, outputs, counterexample);
+            panic!(
+                "stress test failed with seed {}\n--- input (shrunk)\n{}\n--- expected\n{}\n--- actual\n{}",
+                seed,
+                input.trim_end(),
+                expected.trim_end(),
+                actual.trim_end()
+            );
+        }
+    }
+}
+
+/// Runs `solve` on `iters` inputs made by `gen`, and panics if `property` does not hold for the
+/// input and the output.
+///
+/// The inputs are made and shrunk in the same way as [`stress`]. A panic of `solve` is reported
+/// without shrinking, because the panic may be caused by an input that `gen` cannot make.
+#[track_caller]
+pub fn property<T: Display>(
+    iters: u64,
+    mut gen: impl FnMut(&mut Rng) -> T,
+    solve: impl Fn(&str) -> String,
+    property: impl Fn(&str, &str) -> bool,
+) {
+    let counterexample =
+        |input: &str| run_quietly(|| solve(input)).filter(|output| !property(input, output));
+
+    for seed in 0..iters {
+        let mut rng = Rng::new(seed);
+        let input = gen(&mut rng).to_string();
+        let output = match run_quietly(|| solve(&input)) {
+            Some(output) => output,
+            None => panic!(
+                "the program panicked with seed {}\n--- input\n{}",
+                seed,
+                input.trim_end()
+            ),
+        };
+        if !property(&input, &output) {
+            let (input, output) = shrink(&mut gen, rng.into_draws(), input, output, counterexample);
+            panic!(
+                "property does not hold with seed {}\n--- input (shrunk)\n{}\n--- output\n{}",
+                seed,
+                input.trim_end(),
+                output.trim_end()
+            );
+        }
+    }
+}
+
+/// Runs the interactive program `solve` against `judge`, and panics with the transcript if the
+/// judge rejects it or either of them panics.
+///
+/// When the judge returns, the input of the program is closed.
+#[track_caller]
+pub fn interact(solve: impl FnOnce() + Send, judge: impl FnOnce(&mut Judge) -> bool) {
+    let (input_tx, input_rx) = mpsc::channel();
+    let (output_tx, output_rx) = mpsc::channel();
+    let mut judge_io = Judge::new(input_tx, output_rx);
+
+    let (verdict, solved) = thread::scope(|scope| {
+        let handle = scope.spawn(move || {
+            let input = Box::new(BufReader::new(ChannelReader::new(input_rx)));
+            crate::diag::quietly(|| {
+                panic::catch_unwind(AssertUnwindSafe(|| {
+                    crate::io::run_with(input, output_tx, solve)
+                }))
+            })
+        });
+        let verdict = panic::catch_unwind(AssertUnwindSafe(|| judge(&mut judge_io)));
+        judge_io.close();
+        let solved = handle.join().unwrap();
+        (verdict, solved)
+    });
+    judge_io.drain();
+
+    let reason = match (verdict, solved) {
+        (Ok(true), Ok(())) => return,
+        (Ok(false), _) => "rejected by the judge".to_string(),
+        (Err(err), _) => format!("the judge panicked: {}", panic_message(&*err)),
+        (Ok(true), Err(err)) => format!("the program panicked: {}", panic_message(&*err)),
+    };
+    panic!(
+        "interaction failed ({})\n--- transcript (> input, < output)\n{}",
+        reason,
+        judge_io.transcript().trim_end()
+    );
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "(unknown)"
+    }
+}
+
+/// Appends a token of a generated input to `out`.
+pub fn push_token(out: &mut String, token: &impl Display) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push(' ');
+    }
+    out.push_str(&token.to_string());
+}
+
+fn run_quietly<T>(f: impl FnOnce() -> T) -> Option<T> {
+    crate::diag::quietly(|| panic::catch_unwind(AssertUnwindSafe(f)).ok())
+}
+
+// The maximum number of inputs tried to shrink a failing input.
+const SHRINK_LIMIT: usize = 10_000;
+
+// Shrinks the input made from the random values `draws` while `fails` returns the result of a
+// failure. Since the candidates are made by `gen`, they are always well-formed.
+fn shrink<T: Display, R>(
+    gen: &mut impl FnMut(&mut Rng) -> T,
+    mut draws: Vec<u64>,
+    mut input: String,
+    mut result: R,
+    fails: impl Fn(&str) -> Option<R>,
+) -> (String, R) {
+    let mut tried = 0;
+    'shrink: loop {
+        for cand in candidates(&draws) {
+            if tried == SHRINK_LIMIT {
+                break 'shrink;
+            }
+            tried += 1;
+
+            let mut rng = Rng::replay(cand);
+            let cand_input = match run_quietly(|| gen(&mut rng).to_string()) {
+                Some(input) => input,
+                None => continue,
+            };
+            // The random values actually used may differ from the candidate, so they are
+            // checked to be smaller than the current ones to make sure that it terminates.
+            let used = rng.into_draws();
+            if (used.len(), &used) >= (draws.len(), &draws) {
+                continue;
+            }
+            if let Some(r) = fails(&cand_input) {
+                draws = used;
+                input = cand_input;
+                result = r;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+    (input, result)
+}
+
+// Returns the random values one step smaller than `draws`: one of them removed, replaced with
+// zero, or halved.
+fn candidates(draws: &[u64]) -> Vec<Vec<u64>> {
+    let mut ret = vec![];
+    for i in (0..draws.len()).rev() {
+        let mut cand = draws.to_vec();
+        cand.remove(i);
+        ret.push(cand);
+    }
+    for (i, &x) in draws.iter().enumerate() {
+        if x != 0 {
+            let mut cand = draws.to_vec();
+            cand[i] = 0;
+            ret.push(cand);
+        }
+    }
+    for (i, &x) in draws.iter().enumerate() {
+        if x > 1 {
+            let mut cand = draws.to_vec();
+            cand[i] = x / 2;
+            ret.push(cand);
+        }
+    }
+    ret
+}
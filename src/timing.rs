@@ -0,0 +1,178 @@
+//! Timing reports of `#[argio(timing)]` and `#[argio(bench)]`.
+//!
+//! The generated code records the elapsed time of each case, and a summary is printed to stderr at the end.
+
+use crate::{
+    diag::{message, Level},
+    memory::format_size,
+};
+use std::{
+    fmt::Display,
+    fs, process,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Collects the elapsed time of cases.
+pub struct Timing {
+    stats: Mutex<Stats>,
+}
+
+#[derive(Default)]
+struct Stats {
+    cases: usize,
+    total: Duration,
+    max: Option<(usize, Duration)>,
+}
+
+impl Timing {
+    pub fn new() -> Self {
+        Timing {
+            stats: Mutex::new(Stats::default()),
+        }
+    }
+
+    /// Returns the start time of a case.
+    pub fn start(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Records the elapsed time of the case `case` started at `start`.
+    pub fn record(&self, case: Option<usize>, start: Instant) {
+        let elapsed = start.elapsed();
+        match case {
+            Some(case) => message(
+                Level::Info,
+                format_args!("case #{} took {:.3?}", case + 1, elapsed),
+            ),
+            None => message(Level::Info, format_args!("took {:.3?}", elapsed)),
+        }
+
+        let mut stats = self.stats.lock().unwrap();
+        stats.cases += 1;
+        stats.total += elapsed;
+        if let Some(case) = case {
+            if !matches!(stats.max, Some((_, max)) if elapsed <= max) {
+                stats.max = Some((case, elapsed));
+            }
+        }
+    }
+
+    /// Prints the total and the maximum time of the cases.
+    pub fn report(&self) {
+        let stats = self.stats.lock().unwrap();
+        if let Some((case, max)) = stats.max {
+            message(
+                Level::Info,
+                format_args!(
+                    "{} case(s) took {:.3?} in total, max {:.3?} (case #{})",
+                    stats.cases,
+                    stats.total,
+                    max,
+                    case + 1
+                ),
+            );
+        }
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Timing::new()
+    }
+}
+
+/// Measures the whole run of the program.
+pub struct Bench {
+    start: Instant,
+}
+
+impl Bench {
+    pub fn start() -> Self {
+        Bench {
+            start: Instant::now(),
+        }
+    }
+
+    /// Prints the elapsed time and the peak memory usage if available.
+    pub fn report(&self) {
+        let elapsed = self.start.elapsed();
+        match peak_rss() {
+            Some(rss) => message(
+                Level::Info,
+                format_args!("took {:.3?}, peak memory {}", elapsed, format_size(rss)),
+            ),
+            None => message(Level::Info, format_args!("took {:.3?}", elapsed)),
+        }
+    }
+}
+
+/// The deadline of an interactive run given by `#[argio(deadline)]`.
+pub struct Deadline {
+    end: Instant,
+}
+
+impl Deadline {
+    /// Sets the deadline `millis` milliseconds from now.
+    pub fn new(millis: u64) -> Self {
+        Deadline {
+            end: Instant::now() + Duration::from_millis(millis),
+        }
+    }
+
+    /// Returns the time left until the deadline, which is zero after it.
+    pub fn remaining(&self) -> Duration {
+        self.end.saturating_duration_since(Instant::now())
+    }
+
+    /// Prints the value of `hook` as the final answer and exits the program at the deadline, if it has not finished by then.
+    ///
+    /// This is used by `#[argio(on_deadline)]`. Nothing is done while the output is captured, as the program cannot be exited in tests.
+    pub fn watch<T: Display + 'static>(&self, hook: fn() -> T) {
+        if crate::io::captured() {
+            return;
+        }
+        let remaining = self.remaining();
+        thread::spawn(move || {
+            thread::sleep(remaining);
+            message(
+                Level::Warning,
+                format_args!("the deadline has passed; answering with `on_deadline`"),
+            );
+            crate::io::println(format_args!("{}", hook()));
+            crate::io::flush();
+            process::exit(0);
+        });
+    }
+}
+
+/// Runs `f`, and panics if it takes longer than `limit` milliseconds.
+///
+/// This is used by the tests with the time limit of `#[argio(compete)]`. The time is checked only in release builds, as debug builds are much slower than the submitted programs.
+pub fn check<T>(limit: u64, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let ret = f();
+    let elapsed = start.elapsed();
+    if !cfg!(debug_assertions) && elapsed > Duration::from_millis(limit) {
+        panic!(
+            "the run took {:.3?}, exceeding the time limit {:?}",
+            elapsed,
+            Duration::from_millis(limit)
+        );
+    }
+    ret
+}
+
+// Returns the peak resident set size of the process, which is only available on Linux.
+fn peak_rss() -> Option<usize> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find_map(|l| l.strip_prefix("VmHWM:"))?;
+    let kib = line
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+    Some(kib << 10)
+}
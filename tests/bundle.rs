@@ -0,0 +1,51 @@
+use argio::argio;
+use argio::marker::Usize1;
+
+// The file is written when this test is compiled, and it is compiled again on its own by `rustc`.
+#[argio(
+    multicase,
+    input = argio::scan,
+    bundle = "target/tests/bundle.rs",
+    timing,
+    progress
+)]
+#[allow(dead_code)]
+fn main(n: usize, k: Usize1, a: [i64; n]) -> i64 {
+    a[k] * 2
+}
+
+#[test]
+fn bundle() {
+    use std::{io::Write, process};
+
+    let root = env!("CARGO_MANIFEST_DIR");
+    let code = std::fs::read_to_string(format!("{}/target/tests/bundle.rs", root)).unwrap();
+    assert!(code.len() < 64 * 1024, "the bundle is {} bytes", code.len());
+
+    let exe = format!("{}/bundle", env!("CARGO_TARGET_TMPDIR"));
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let status = process::Command::new(rustc)
+        .args(["--edition", "2018", "-D", "warnings", "-o", &exe])
+        .arg(format!("{}/target/tests/bundle.rs", root))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let mut child = process::Command::new(&exe)
+        .stdin(process::Stdio::piped())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::null())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"2\n3 2\n1 2 3\n2 1\n5 4\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Case #1: 4\nCase #2: 10\n"
+    );
+}
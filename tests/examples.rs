@@ -0,0 +1,72 @@
+use std::io::Write;
+use std::process;
+
+// Runs an example by `cargo run`, as the lines printed by `println!` outside of the function are
+// not captured by `#[argio::test]`.
+fn run(example: &str, args: &[&str], input: &str, envs: &[(&str, &str)]) -> String {
+    let mut child = process::Command::new(env!("CARGO"))
+        .args(["run", "-q", "--example", example, "--"])
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .envs(envs.iter().copied())
+        .stdin(process::Stdio::piped())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::inherit())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn helper_print() {
+    assert_eq!(
+        run("helper-print", &[], "2\n1\n2\n", &[]),
+        "Case #1: 2\nCase #2: 4\n"
+    );
+}
+
+#[test]
+fn fastout() {
+    for example in ["fastout", "fastout-expanded"] {
+        assert_eq!(
+            run(example, &[], "2\n1\n2\n", &[]),
+            "2\n4\nCase #1: Case #2: "
+        );
+    }
+}
+
+#[test]
+fn separator_with_selected_cases() {
+    let input = "3\n1\n2\n3\n";
+    for env in [("ARGIO_ONLY_CASE", "2"), ("ARGIO_CASES", "2..3")] {
+        assert_eq!(
+            run("multicase-separator", &[], input, &[env]),
+            "Case #2: 4\n"
+        );
+    }
+    assert_eq!(
+        run("multicase-separator", &[], input, &[("ARGIO_CASES", "2..")]),
+        "Case #2: 4\n\nCase #3: 6\n"
+    );
+}
+
+#[test]
+fn input_file_arg() {
+    let path = format!("{}/input-file-arg.txt", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&path, "3\n1 2 3\n").unwrap();
+    assert_eq!(run("input-file-arg", &[&path], "", &[]), "6\n");
+    assert_eq!(run("input-file-arg", &[], "2\n4 5\n", &[]), "9\n");
+    // Without `input_file_arg`, the command line arguments are not taken as the input file.
+    assert_eq!(
+        run("helper-print", &["no-such-file.txt"], "1\n3\n", &[]),
+        "Case #1: 6\n"
+    );
+}
@@ -0,0 +1,43 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+// The program writes to a buffered TCP stream here, so it only gets an answer to each query if
+// `println!` flushes it.
+#[test]
+fn interactive_over_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut child = Command::new(env!("CARGO"))
+        .args(["run", "-q", "--example", "interactive"])
+        .env("ARGIO_CONNECT", addr.to_string())
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let (stream, _) = listener.accept().unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    let secret = 42;
+    writeln!(writer, "100").unwrap();
+    let mut queries = 0;
+    loop {
+        let mut query = String::new();
+        reader.read_line(&mut query).unwrap();
+        let x = query.trim_end().strip_prefix("? ").unwrap();
+        let x = x.parse::<u64>().unwrap();
+        queries += 1;
+        if x == secret {
+            writeln!(writer, "=").unwrap();
+            break;
+        }
+        writeln!(writer, "{}", if secret < x { "<" } else { ">" }).unwrap();
+    }
+
+    assert!(child.wait().unwrap().success());
+    assert!(queries <= 7);
+}
@@ -0,0 +1,53 @@
+use argio::scan::{ByteScanner, Source};
+
+// Reads all the tokens of `input` with `ByteScanner`, and checks them against `str::split`.
+fn check(input: &str) {
+    let mut scanner = ByteScanner::new(input.as_bytes());
+    let mut tokens = vec![];
+    while let Some(token) = scanner.next_token() {
+        tokens.push(token.to_string());
+    }
+    let expected = input
+        .split(|c: char| c.is_ascii_whitespace())
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>();
+    assert_eq!(tokens, expected, "input: {:?}", input);
+}
+
+#[test]
+fn tokens_across_words() {
+    // The tokens start and end at every offset in the words of eight bytes.
+    for sep in [" ", "\n", "\t", "\r\n", "\x0c", "  \n "] {
+        let input = (1..=20)
+            .map(|len| "x".repeat(len))
+            .collect::<Vec<_>>()
+            .join(sep);
+        check(&input);
+        check(&format!("{}{}", sep, input));
+        check(&format!("{}{}", input, sep));
+    }
+}
+
+#[test]
+fn tails_shorter_than_word() {
+    for len in 0..=16 {
+        let token = "7".repeat(len);
+        check(&token);
+        check(&format!("1 {}", token));
+        check(&format!("123456789 {}", token));
+        check(&format!("{} 1", token));
+    }
+}
+
+#[test]
+fn control_bytes_in_tokens() {
+    // The bytes below `0x21` other than whitespace, such as the vertical tab, are part of tokens,
+    // and so are the bytes of non-ASCII characters.
+    for c in ['\x00', '\x01', '\x0b', '\x1f', '\x7f', 'é', 'あ'] {
+        for pos in 0..=16 {
+            let token = format!("{}{}{}", "a".repeat(pos), c, "b".repeat(16 - pos));
+            check(&token);
+            check(&format!("{} {}\n{}", token, token, c));
+        }
+    }
+}
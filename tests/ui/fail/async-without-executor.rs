@@ -0,0 +1,4 @@
+#[argio::argio]
+async fn main(n: usize) -> usize {
+    n
+}
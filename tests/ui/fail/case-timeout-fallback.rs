@@ -0,0 +1,4 @@
+#[argio::argio(multicase(case_timeout = "1s"))]
+fn main(n: usize) -> usize {
+    n
+}
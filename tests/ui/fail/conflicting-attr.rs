@@ -0,0 +1,4 @@
+#[argio::argio(input_str = "1\n", files)]
+fn main(n: usize) -> usize {
+    n
+}
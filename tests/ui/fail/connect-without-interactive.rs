@@ -0,0 +1,4 @@
+#[argio::argio(connect = "localhost:8000")]
+fn main(n: u64) -> u64 {
+    n
+}
@@ -0,0 +1,4 @@
+#[argio::argio(deadline = "1900ms")]
+fn main(n: usize) -> usize {
+    n
+}
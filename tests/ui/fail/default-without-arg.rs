@@ -0,0 +1,4 @@
+#[argio::argio]
+fn main(#[argio(default = 10)] n: usize) -> usize {
+    n
+}
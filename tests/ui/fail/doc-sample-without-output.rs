@@ -0,0 +1,7 @@
+/// ```input
+/// 1 2
+/// ```
+#[argio::argio]
+fn main(a: i64, b: i64) -> i64 {
+    a + b
+}
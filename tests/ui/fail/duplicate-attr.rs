@@ -0,0 +1,4 @@
+#[argio::argio(multicase, timing, multicase = "#{case}: ")]
+fn main(n: usize) -> usize {
+    n
+}
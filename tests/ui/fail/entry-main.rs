@@ -0,0 +1,4 @@
+#[argio::argio(entry)]
+fn main(n: usize) -> usize {
+    n
+}
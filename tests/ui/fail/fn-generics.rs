@@ -0,0 +1,4 @@
+#[argio::argio]
+fn main<T: std::str::FromStr>(x: T) -> usize {
+    0
+}
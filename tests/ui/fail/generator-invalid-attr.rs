@@ -0,0 +1,4 @@
+#[argio::generator(seed = 1)]
+fn main(rng: &mut argio::random::Rng) -> u64 {
+    rng.range(1..=10)
+}
@@ -0,0 +1,4 @@
+#[argio::generator]
+fn main(seed: u64, n: usize) -> String {
+    format!("{} {}", seed, n)
+}
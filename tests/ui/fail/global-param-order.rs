@@ -0,0 +1,4 @@
+#[argio::argio(multicase)]
+fn main(n: usize, #[argio(global)] m: usize) -> usize {
+    n % m
+}
@@ -0,0 +1,4 @@
+#[argio::argio(multicase = "Case #{i + 1}}: ")]
+fn main(n: usize) -> usize {
+    n
+}
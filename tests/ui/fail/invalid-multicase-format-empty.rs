@@ -0,0 +1,4 @@
+#[argio::argio(multicase = "Case #{}: ")]
+fn main(n: usize) -> usize {
+    n
+}
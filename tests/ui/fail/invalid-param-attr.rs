@@ -0,0 +1,4 @@
+#[argio::argio]
+fn main(#[argio(form_str)] n: usize) -> usize {
+    n
+}
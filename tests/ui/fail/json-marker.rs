@@ -0,0 +1,4 @@
+#[argio::argio(format = json)]
+fn main(k: argio::marker::Usize1) -> usize {
+    k
+}
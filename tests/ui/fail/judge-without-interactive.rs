@@ -0,0 +1,8 @@
+#[argio::argio(judge = judge)]
+fn main(n: usize) -> usize {
+    n
+}
+
+fn judge(_: &mut argio::judge::Judge) -> bool {
+    true
+}
@@ -0,0 +1,4 @@
+#[argio::argio(multicase(eof, count = 10))]
+fn main(n: usize) -> usize {
+    n
+}
@@ -0,0 +1,4 @@
+#[argio::argio(multicase(first_line = "t: u64, modulo: u64"))]
+fn main(a: u64, b: u64) -> u64 {
+    a * b % modulo
+}
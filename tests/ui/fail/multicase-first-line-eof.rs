@@ -0,0 +1,4 @@
+#[argio::argio(multicase(eof, first_line = "t: usize, k: usize"))]
+fn main(n: usize) -> usize {
+    n * k
+}
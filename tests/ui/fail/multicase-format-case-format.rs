@@ -0,0 +1,4 @@
+#[argio::argio(multicase(format = "Case #{i+1}: ", case_format = "Case #{i+1}: {ret}"))]
+fn main(n: usize) -> usize {
+    n
+}
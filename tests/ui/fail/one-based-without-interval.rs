@@ -0,0 +1,4 @@
+#[argio::argio]
+fn main(n: usize, #[argio(one_based)] a: [usize; n]) -> usize {
+    a.into_iter().sum()
+}
@@ -0,0 +1,4 @@
+#[argio::argio(flush = never, output_capacity = 1 << 20)]
+fn main(n: usize) -> usize {
+    n
+}
@@ -0,0 +1,4 @@
+#[argio::argio]
+fn main(#[argio(range = 0..10)] (a, b): (usize, usize)) -> usize {
+    a + b
+}
@@ -0,0 +1,4 @@
+#[argio::argio]
+fn main(n: usize, s: &str) -> usize {
+    n + s.len()
+}
@@ -0,0 +1,8 @@
+struct P {
+    x: i64,
+}
+
+#[argio::argio]
+fn main(P { x }: P) -> i64 {
+    x
+}
@@ -0,0 +1,7 @@
+struct Point;
+
+#[argio::argio]
+#[argio::property(check = |_, _| true)]
+fn main(p: Point) {
+    let _ = p;
+}
@@ -0,0 +1,4 @@
+#[argio::argio(sets = "Set #{i+1}:\n")]
+fn main(n: usize) -> usize {
+    n
+}
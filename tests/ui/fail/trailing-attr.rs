@@ -0,0 +1,4 @@
+#[argio::argio(multicase; timing)]
+fn main(n: usize) -> usize {
+    n
+}
@@ -0,0 +1,4 @@
+#[argio::argio]
+fn main(n: usize, #[argio(until_blank)] a: [u64; n]) -> u64 {
+    a.into_iter().sum()
+}